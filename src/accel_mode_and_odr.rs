@@ -2,8 +2,9 @@ use embedded_hal::blocking::delay::DelayUs;
 
 use crate::{
     interface::{ReadData, WriteData},
-    register_address::{CtrlReg1A, CtrlReg4A},
-    AccelMode, AccelOutputDataRate, AccelScale, Error, Lsm303agr,
+    register_address::{CtrlReg1A, CtrlReg2A, CtrlReg4A, ReferenceA},
+    AccelHighPassCutoff, AccelHighPassMode, AccelMode, AccelOutputDataRate, AccelScale,
+    Acceleration, Error, Lsm303agr,
 };
 
 impl<DI, CommE, PinE, MODE> Lsm303agr<DI, MODE>
@@ -22,6 +23,21 @@ where
         delay: &mut D,
         odr: AccelOutputDataRate,
     ) -> Result<(), Error<CommE, PinE>> {
+        let change_time = self.set_accel_odr_no_wait(odr)?;
+        delay.delay_us(change_time);
+
+        Ok(())
+    }
+
+    /// Set accelerometer output data rate without waiting for the change to take effect.
+    ///
+    /// This behaves like [`set_accel_odr()`](Lsm303agr::set_accel_odr), but instead of
+    /// blocking, it returns the time in µs the caller is responsible for waiting before
+    /// the output data is valid.
+    pub fn set_accel_odr_no_wait(
+        &mut self,
+        odr: AccelOutputDataRate,
+    ) -> Result<u32, Error<CommE, PinE>> {
         let old_mode = self.get_accel_mode();
 
         let reg1 = self.ctrl_reg1_a.with_odr(odr);
@@ -34,15 +50,12 @@ where
             }
         }
 
-        self.iface.write_accel_register(reg1)?;
+        self.write_accel_register(reg1)?;
         self.ctrl_reg1_a = reg1;
         self.accel_odr = Some(odr);
 
         let mode = self.get_accel_mode();
-        let change_time = old_mode.change_time_us(mode, odr);
-        delay.delay_us(change_time);
-
-        Ok(())
+        Ok(old_mode.change_time_us(mode, odr))
     }
 
     /// Set accelerometer power/resolution mode
@@ -50,24 +63,70 @@ where
     /// Returns `Error::InvalidInputData` if the mode is incompatible with the current
     /// accelerometer output data rate.
     ///
+    /// The time waited for is the one returned by
+    /// [`set_accel_mode_no_wait()`](Lsm303agr::set_accel_mode_no_wait).
+    ///
     #[doc = include_str!("delay.md")]
     pub fn set_accel_mode<D: DelayUs<u32>>(
         &mut self,
         delay: &mut D,
         mode: AccelMode,
     ) -> Result<(), Error<CommE, PinE>> {
+        let change_time = self.set_accel_mode_no_wait(mode)?;
+        delay.delay_us(change_time);
+
+        Ok(())
+    }
+
+    /// Set accelerometer power/resolution mode without waiting for the change to take effect.
+    ///
+    /// This behaves like [`set_accel_mode()`](Lsm303agr::set_accel_mode), but instead of
+    /// blocking, it returns the time in µs the caller is responsible for waiting before
+    /// the output data is valid.
+    ///
+    /// No time needs to be waited, i.e. `0` is returned, when changing into power-down mode,
+    /// when no output data rate is set or when the mode does not change. Otherwise, the
+    /// time depends on the transition and the current output data rate:
+    ///
+    /// | Transition                                   | Time (µs)      |
+    /// |----------------------------------------------|----------------|
+    /// | power-down → low-power                       | 1000           |
+    /// | power-down → normal                          | 1600           |
+    /// | power-down → high-resolution                 | 7 samples      |
+    /// | normal/low-power → high-resolution           | 7 samples      |
+    /// | high-resolution → normal/low-power           | 1 sample       |
+    /// | normal → low-power, low-power → normal       | 1 sample       |
+    ///
+    /// with the following sample times per output data rate:
+    ///
+    /// | Output data rate    | 1 sample  | 7 samples |
+    /// |---------------------|-----------|-----------|
+    /// | 1 Hz                | 1000000   | 7000000   |
+    /// | 10 Hz               | 100000    | 700000    |
+    /// | 25 Hz               | 40000     | 280000    |
+    /// | 50 Hz               | 20000     | 140000    |
+    /// | 100 Hz              | 10000     | 70000     |
+    /// | 200 Hz              | 5000      | 35000     |
+    /// | 400 Hz              | 2500      | 17500     |
+    /// | 1.344 kHz           | 745       | 5209      |
+    /// | 1.620 kHz (LP only) | 618       | 4321      |
+    /// | 5.376 kHz (LP only) | 187       | 1303      |
+    pub fn set_accel_mode_no_wait(&mut self, mode: AccelMode) -> Result<u32, Error<CommE, PinE>> {
         check_accel_odr_is_compatible_with_mode(self.accel_odr, mode)?;
 
         let old_mode = self.get_accel_mode();
 
+        // Each register is written exactly once. `CTRL_REG4_A` is written last when
+        // enabling high-resolution mode and first otherwise, so that low-power and
+        // high-resolution mode are never enabled at the same time.
         match mode {
             AccelMode::HighResolution => {
                 self.disable_lp()?;
                 self.enable_hr()?;
             }
             AccelMode::Normal => {
-                self.disable_lp()?;
                 self.disable_hr()?;
+                self.disable_lp()?;
             }
             AccelMode::LowPower => {
                 self.disable_hr()?;
@@ -75,18 +134,15 @@ where
             }
             AccelMode::PowerDown => {
                 let reg1 = self.ctrl_reg1_a.difference(CtrlReg1A::ODR);
-                self.iface.write_accel_register(reg1)?;
+                self.write_accel_register(reg1)?;
                 self.ctrl_reg1_a = reg1;
                 self.accel_odr = None;
             }
         }
 
-        if let Some(odr) = self.accel_odr {
-            let change_time = old_mode.change_time_us(mode, odr);
-            delay.delay_us(change_time);
-        }
-
-        Ok(())
+        Ok(self
+            .accel_odr
+            .map_or(0, |odr| old_mode.change_time_us(mode, odr)))
     }
 
     /// Get the accelerometer mode
@@ -106,6 +162,63 @@ where
         }
     }
 
+    /// Check whether the accelerometer is ready to provide valid data.
+    ///
+    /// Returns `false` if the accelerometer is powered down. Otherwise, returns
+    /// whether new data is available for all axes.
+    ///
+    /// After leaving power-down mode, the first valid sample is available after
+    /// the turn-on time, which is 1 ms in low-power mode, 1.6 ms in normal mode
+    /// and 7/ODR in high-resolution mode.
+    pub fn acc_is_ready(&mut self) -> Result<bool, Error<CommE, PinE>> {
+        if self.get_accel_mode() == AccelMode::PowerDown {
+            return Ok(false);
+        }
+
+        Ok(self.accel_status()?.xyz_new_data())
+    }
+
+    /// Read acceleration samples into a buffer.
+    ///
+    /// For each sample, this waits for new data by polling the accelerometer
+    /// status, waiting one sample period at the configured output data rate if
+    /// no new data is available yet. If there is still no new data after that,
+    /// reading stops early.
+    ///
+    /// Returns the number of samples read. These are stored at the start of `out`
+    /// and the remaining entries are set to `None`.
+    ///
+    /// Returns `Error::InvalidInputData` if the accelerometer is powered down.
+    pub fn acceleration_batch<D: DelayUs<u32>>(
+        &mut self,
+        out: &mut [Option<Acceleration>],
+        delay: &mut D,
+    ) -> Result<usize, Error<CommE, PinE>> {
+        let odr = self.accel_odr.ok_or(Error::InvalidInputData)?;
+        // 1/ODR in µs
+        let sample_period = odr.turn_on_time_us_frac_1();
+
+        let mut count = 0;
+        while count < out.len() {
+            if !self.accel_status()?.xyz_new_data() {
+                delay.delay_us(sample_period);
+
+                if !self.accel_status()?.xyz_new_data() {
+                    break;
+                }
+            }
+
+            out[count] = Some(self.acceleration()?);
+            count += 1;
+        }
+
+        for sample in &mut out[count..] {
+            *sample = None;
+        }
+
+        Ok(count)
+    }
+
     /// Set accelerometer scaling factor
     ///
     /// This changes the scale at which the acceleration is read.
@@ -113,7 +226,7 @@ where
     /// where g is the gravity of the earth (~9.82 m/s²).
     pub fn set_accel_scale(&mut self, scale: AccelScale) -> Result<(), Error<CommE, PinE>> {
         let reg4 = self.ctrl_reg4_a.with_scale(scale);
-        self.iface.write_accel_register(reg4)?;
+        self.write_accel_register(reg4)?;
         self.ctrl_reg4_a = reg4;
         Ok(())
     }
@@ -123,30 +236,100 @@ where
         self.ctrl_reg4_a.scale()
     }
 
+    /// Get accelerometer output data rate
+    ///
+    /// Returns `None` if the accelerometer is powered down.
+    pub fn get_accel_odr(&self) -> Option<AccelOutputDataRate> {
+        self.accel_odr
+    }
+
+    /// Get accelerometer output data rate in Hz
+    ///
+    /// Returns `None` if the accelerometer is powered down.
+    pub fn get_accel_odr_hz(&self) -> Option<u16> {
+        self.accel_odr.map(u16::from)
+    }
+
+    /// Configure accelerometer high-pass filter
+    ///
+    /// The cut-off frequency is relative to the accelerometer output data rate.
+    /// If `filtered_data` is `true`, the filtered data is sent to the output
+    /// registers and the FIFO, which e.g. removes the gravity offset.
+    pub fn set_accel_high_pass_filter(
+        &mut self,
+        mode: AccelHighPassMode,
+        cutoff: AccelHighPassCutoff,
+        filtered_data: bool,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let mut reg2 = self
+            .ctrl_reg2_a
+            .with_high_pass_mode(mode)
+            .with_high_pass_cutoff(cutoff);
+        reg2.set(CtrlReg2A::FDS, filtered_data);
+        self.write_accel_register(reg2)?;
+        self.ctrl_reg2_a = reg2;
+        Ok(())
+    }
+
+    /// Use the current acceleration as the zero reference.
+    ///
+    /// This sets the high-pass filter to [`AccelHighPassMode::NormalWithReset`],
+    /// sends the filtered data to the output registers and resets the filter by reading
+    /// the `REFERENCE/DATACAPTURE_A` register, effectively taring the accelerometer to
+    /// its current orientation. Afterwards, it waits for one sample period so that the
+    /// next sample is relative to the new reference.
+    ///
+    /// This removes the gravity component from the output, so measurements only
+    /// contain the acceleration caused by motion. The configured high-pass filter
+    /// cut-off frequency is kept, so slow orientation changes are removed as well.
+    ///
+    /// Returns `Error::InvalidInputData` if the accelerometer is powered down.
+    pub fn acc_autozero<D: DelayUs<u32>>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let odr = self.accel_odr.ok_or(Error::InvalidInputData)?;
+
+        let reg2 = self
+            .ctrl_reg2_a
+            .with_high_pass_mode(AccelHighPassMode::NormalWithReset)
+            .union(CtrlReg2A::FDS);
+        self.write_accel_register(reg2)?;
+        self.ctrl_reg2_a = reg2;
+
+        // Reading the reference register resets the filter to the current acceleration.
+        self.iface.read_accel_register::<ReferenceA>()?;
+
+        // Wait for a filtered sample, 1/ODR in µs.
+        delay.delay_us(odr.turn_on_time_us_frac_1());
+
+        Ok(())
+    }
+
     fn enable_hr(&mut self) -> Result<(), Error<CommE, PinE>> {
         let reg4 = self.ctrl_reg4_a.union(CtrlReg4A::HR);
-        self.iface.write_accel_register(reg4)?;
+        self.write_accel_register(reg4)?;
         self.ctrl_reg4_a = reg4;
         Ok(())
     }
 
     fn disable_hr(&mut self) -> Result<(), Error<CommE, PinE>> {
         let reg4 = self.ctrl_reg4_a.difference(CtrlReg4A::HR);
-        self.iface.write_accel_register(reg4)?;
+        self.write_accel_register(reg4)?;
         self.ctrl_reg4_a = reg4;
         Ok(())
     }
 
     fn enable_lp(&mut self) -> Result<(), Error<CommE, PinE>> {
         let reg1 = self.ctrl_reg1_a.union(CtrlReg1A::LPEN);
-        self.iface.write_accel_register(reg1)?;
+        self.write_accel_register(reg1)?;
         self.ctrl_reg1_a = reg1;
         Ok(())
     }
 
     fn disable_lp(&mut self) -> Result<(), Error<CommE, PinE>> {
         let reg1 = self.ctrl_reg1_a.difference(CtrlReg1A::LPEN);
-        self.iface.write_accel_register(reg1)?;
+        self.write_accel_register(reg1)?;
         self.ctrl_reg1_a = reg1;
         Ok(())
     }
@@ -1,32 +1,132 @@
+use core::convert::TryFrom;
+
+use embedded_hal::blocking::delay::DelayUs;
+
+#[cfg(feature = "fake-interface")]
+use crate::interface::FakeInterface;
+
 use crate::{
-    interface::{I2cInterface, ReadData, SpiInterface, WriteData},
+    interface::{DynInterface, I2cInterface, ReadData, SpiInterface, WriteData},
     mode,
     register_address::{
-        CfgRegAM, CfgRegBM, CfgRegCM, CtrlReg1A, CtrlReg3A, CtrlReg4A, CtrlReg5A, FifoCtrlRegA,
-        StatusRegA, StatusRegAuxA, StatusRegM, TempCfgRegA, WhoAmIA, WhoAmIM,
+        accel_unverified_bits, mag_unverified_bits, ActDurA, ActThsA, CfgRegAM, CfgRegBM, CfgRegCM,
+        ClickCfgA, ClickThsA, CtrlReg1A, CtrlReg2A, CtrlReg3A, CtrlReg4A, CtrlReg5A, CtrlReg6A,
+        FifoCtrlRegA, Int1CfgA, Int1DurationA, Int1SrcA, Int1ThsA, Int2CfgA, Int2DurationA,
+        Int2SrcA, Int2ThsA, IntCrtlRegM, IntSourceRegM, OutXA, OutYA, OutZA, ReferenceA, RegWrite,
+        StatusRegA, StatusRegAuxA, StatusRegM, TempCfgRegA, TimeLatencyA, TimeLimitA, TimeWindowA,
+        WhoAmIA, WhoAmIM,
     },
-    Acceleration, AccelerometerId, Error, FifoMode, Interrupt, Lsm303agr, MagnetometerId,
-    PhantomData, Status, Temperature, TemperatureStatus,
+    AccelClickConfig, AccelInterruptConfig, AccelInterruptSource, AccelMode, AccelOutputDataRate,
+    AccelScale, AccelUnit, Acceleration, AccelerometerId, Axis, DeviceConfig, Error, FifoMode,
+    Interrupt, InterruptLine, InterruptSource, Lsm303agr, MagInterruptConfig, MagInterruptSource,
+    MagneticField, MagnetometerId, MilliG, PhantomData, RegisterDump, Status, TempSource,
+    Temperature, TemperatureStatus,
 };
 
+/// Boot time after rebooting the memory content.
+pub(crate) const BOOT_TIME_US: u32 = 5000;
+
 impl<I2C> Lsm303agr<I2cInterface<I2C>, mode::MagOneShot> {
     /// Create new instance of the LSM303AGR device communicating through I2C.
     pub fn new_with_i2c(i2c: I2C) -> Self {
         Lsm303agr {
             iface: I2cInterface { i2c },
             ctrl_reg1_a: CtrlReg1A::default(),
+            ctrl_reg2_a: CtrlReg2A::default(),
             ctrl_reg3_a: CtrlReg3A::default(),
             ctrl_reg4_a: CtrlReg4A::default(),
             ctrl_reg5_a: CtrlReg5A::default(),
+            ctrl_reg6_a: CtrlReg6A::default(),
             cfg_reg_a_m: CfgRegAM::default(),
             cfg_reg_b_m: CfgRegBM::default(),
             cfg_reg_c_m: CfgRegCM::default(),
+            int_crtl_reg_m: IntCrtlRegM::default(),
             temp_cfg_reg_a: TempCfgRegA::default(),
             fifo_ctrl_reg_a: FifoCtrlRegA::default(),
             accel_odr: None,
+            mag_one_shot_triggered: false,
+            verify_writes: false,
+            _mag_mode: PhantomData,
+        }
+    }
+}
+
+impl<DI, MODE> Lsm303agr<DI, MODE> {
+    /// Destroy driver instance, return the interface and the cached device configuration.
+    ///
+    /// The driver can be recreated later using [`from_parts`](Self::from_parts)
+    /// without reading back the configuration, e.g. after the microcontroller was
+    /// powered down while the device retained its configuration.
+    pub fn into_parts(self) -> (DI, DeviceConfig<MODE>) {
+        let config = DeviceConfig {
+            ctrl_reg1_a: self.ctrl_reg1_a,
+            ctrl_reg2_a: self.ctrl_reg2_a,
+            ctrl_reg3_a: self.ctrl_reg3_a,
+            ctrl_reg4_a: self.ctrl_reg4_a,
+            ctrl_reg5_a: self.ctrl_reg5_a,
+            ctrl_reg6_a: self.ctrl_reg6_a,
+            cfg_reg_a_m: self.cfg_reg_a_m,
+            cfg_reg_b_m: self.cfg_reg_b_m,
+            cfg_reg_c_m: self.cfg_reg_c_m,
+            int_crtl_reg_m: self.int_crtl_reg_m,
+            temp_cfg_reg_a: self.temp_cfg_reg_a,
+            fifo_ctrl_reg_a: self.fifo_ctrl_reg_a,
+            accel_odr: self.accel_odr,
+            mag_one_shot_triggered: self.mag_one_shot_triggered,
+            verify_writes: self.verify_writes,
+            _mag_mode: PhantomData,
+        };
+
+        (self.iface, config)
+    }
+
+    /// Create new instance from an interface and a device configuration
+    /// returned by [`into_parts`](Self::into_parts).
+    ///
+    /// No communication with the device takes place, so the configuration must
+    /// still match the one stored on the device.
+    pub fn from_parts(iface: DI, config: DeviceConfig<MODE>) -> Self {
+        Lsm303agr {
+            iface,
+            ctrl_reg1_a: config.ctrl_reg1_a,
+            ctrl_reg2_a: config.ctrl_reg2_a,
+            ctrl_reg3_a: config.ctrl_reg3_a,
+            ctrl_reg4_a: config.ctrl_reg4_a,
+            ctrl_reg5_a: config.ctrl_reg5_a,
+            ctrl_reg6_a: config.ctrl_reg6_a,
+            cfg_reg_a_m: config.cfg_reg_a_m,
+            cfg_reg_b_m: config.cfg_reg_b_m,
+            cfg_reg_c_m: config.cfg_reg_c_m,
+            int_crtl_reg_m: config.int_crtl_reg_m,
+            temp_cfg_reg_a: config.temp_cfg_reg_a,
+            fifo_ctrl_reg_a: config.fifo_ctrl_reg_a,
+            accel_odr: config.accel_odr,
+            mag_one_shot_triggered: config.mag_one_shot_triggered,
+            verify_writes: config.verify_writes,
             _mag_mode: PhantomData,
         }
     }
+
+    /// Get the values of the control and configuration registers cached by the driver.
+    ///
+    /// No communication with the device takes place, so this shows the configuration
+    /// the driver expects, e.g. for comparing it with the one read from the device.
+    pub fn debug_registers(&self) -> RegisterDump {
+        RegisterDump {
+            ctrl_reg1_a: self.ctrl_reg1_a.bits(),
+            ctrl_reg2_a: self.ctrl_reg2_a.bits(),
+            ctrl_reg3_a: self.ctrl_reg3_a.bits(),
+            ctrl_reg4_a: self.ctrl_reg4_a.bits(),
+            ctrl_reg5_a: self.ctrl_reg5_a.bits(),
+            ctrl_reg6_a: self.ctrl_reg6_a.bits(),
+            temp_cfg_reg_a: self.temp_cfg_reg_a.bits(),
+            fifo_ctrl_reg_a: self.fifo_ctrl_reg_a.bits(),
+            cfg_reg_a_m: self.cfg_reg_a_m.bits(),
+            cfg_reg_b_m: self.cfg_reg_b_m.bits(),
+            cfg_reg_c_m: self.cfg_reg_c_m.bits(),
+            int_crtl_reg_m: self.int_crtl_reg_m.bits(),
+        }
+    }
 }
 
 impl<I2C, MODE> Lsm303agr<I2cInterface<I2C>, MODE> {
@@ -46,15 +146,20 @@ impl<SPI, CSXL, CSMAG> Lsm303agr<SpiInterface<SPI, CSXL, CSMAG>, mode::MagOneSho
                 cs_mag: chip_select_mag,
             },
             ctrl_reg1_a: CtrlReg1A::default(),
+            ctrl_reg2_a: CtrlReg2A::default(),
             ctrl_reg3_a: CtrlReg3A::default(),
             ctrl_reg4_a: CtrlReg4A::default(),
             ctrl_reg5_a: CtrlReg5A::default(),
+            ctrl_reg6_a: CtrlReg6A::default(),
             cfg_reg_a_m: CfgRegAM::default(),
             cfg_reg_b_m: CfgRegBM::default(),
             cfg_reg_c_m: CfgRegCM::default(),
+            int_crtl_reg_m: IntCrtlRegM::default(),
             temp_cfg_reg_a: TempCfgRegA::default(),
             fifo_ctrl_reg_a: FifoCtrlRegA::default(),
             accel_odr: None,
+            mag_one_shot_triggered: false,
+            verify_writes: false,
             _mag_mode: PhantomData,
         }
     }
@@ -67,6 +172,179 @@ impl<SPI, CSXL, CSMAG, MODE> Lsm303agr<SpiInterface<SPI, CSXL, CSMAG>, MODE> {
     }
 }
 
+impl<'a, CommE, PinE> Lsm303agr<DynInterface<'a, CommE, PinE>, mode::MagOneShot> {
+    /// Create new instance of the LSM303AGR device using an interface selected at runtime.
+    pub fn new_with_dyn(iface: DynInterface<'a, CommE, PinE>) -> Self {
+        Lsm303agr {
+            iface,
+            ctrl_reg1_a: CtrlReg1A::default(),
+            ctrl_reg2_a: CtrlReg2A::default(),
+            ctrl_reg3_a: CtrlReg3A::default(),
+            ctrl_reg4_a: CtrlReg4A::default(),
+            ctrl_reg5_a: CtrlReg5A::default(),
+            ctrl_reg6_a: CtrlReg6A::default(),
+            cfg_reg_a_m: CfgRegAM::default(),
+            cfg_reg_b_m: CfgRegBM::default(),
+            cfg_reg_c_m: CfgRegCM::default(),
+            int_crtl_reg_m: IntCrtlRegM::default(),
+            temp_cfg_reg_a: TempCfgRegA::default(),
+            fifo_ctrl_reg_a: FifoCtrlRegA::default(),
+            accel_odr: None,
+            mag_one_shot_triggered: false,
+            verify_writes: false,
+            _mag_mode: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "fake-interface")]
+impl Lsm303agr<FakeInterface, mode::MagOneShot> {
+    /// Create new instance of the LSM303AGR device using an in-memory register bank.
+    pub fn new_with_fake(fake: FakeInterface) -> Self {
+        Lsm303agr {
+            iface: fake,
+            ctrl_reg1_a: CtrlReg1A::default(),
+            ctrl_reg2_a: CtrlReg2A::default(),
+            ctrl_reg3_a: CtrlReg3A::default(),
+            ctrl_reg4_a: CtrlReg4A::default(),
+            ctrl_reg5_a: CtrlReg5A::default(),
+            ctrl_reg6_a: CtrlReg6A::default(),
+            cfg_reg_a_m: CfgRegAM::default(),
+            cfg_reg_b_m: CfgRegBM::default(),
+            cfg_reg_c_m: CfgRegCM::default(),
+            int_crtl_reg_m: IntCrtlRegM::default(),
+            temp_cfg_reg_a: TempCfgRegA::default(),
+            fifo_ctrl_reg_a: FifoCtrlRegA::default(),
+            accel_odr: None,
+            mag_one_shot_triggered: false,
+            verify_writes: false,
+            _mag_mode: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "fake-interface")]
+impl<MODE> Lsm303agr<FakeInterface, MODE> {
+    /// Get the in-memory register bank.
+    pub fn fake(&self) -> &FakeInterface {
+        &self.iface
+    }
+
+    /// Get the in-memory register bank mutably, e.g. to set measurement data.
+    pub fn fake_mut(&mut self) -> &mut FakeInterface {
+        &mut self.iface
+    }
+
+    /// Destroy driver instance, return the in-memory register bank.
+    pub fn destroy(self) -> FakeInterface {
+        self.iface
+    }
+}
+
+impl<DI, CommE, PinE> Lsm303agr<DI, mode::MagOneShot>
+where
+    DI: ReadData<Error = Error<CommE, PinE>> + WriteData<Error = Error<CommE, PinE>>,
+{
+    /// Reset the device to its power-on defaults.
+    ///
+    /// This reboots the accelerometer memory content, soft-resets and reboots the
+    /// magnetometer and then waits 5 ms for the device to boot. Since rebooting the
+    /// accelerometer only reloads its trimming parameters, the default values of all
+    /// accelerometer control, FIFO, interrupt, click and activity registers are then
+    /// written explicitly. Afterwards, all cached register values are reset to their
+    /// defaults, so [`init()`](Lsm303agr::init) needs to be called again.
+    ///
+    /// This is only available in one-shot mode, since the magnetometer is in idle mode
+    /// after the reset, which would not match the continuous mode type. To reset a
+    /// device in continuous mode, first change it into one-shot mode using
+    /// [`into_mag_one_shot()`](Lsm303agr::into_mag_one_shot).
+    ///
+    /// The next call to [`magnetic_field()`](Lsm303agr::magnetic_field) always starts
+    /// a new measurement.
+    pub fn reset<D: DelayUs<u32>>(&mut self, delay: &mut D) -> Result<(), Error<CommE, PinE>> {
+        self.write_accel_register(CtrlReg5A::default() | CtrlReg5A::BOOT)?;
+        self.write_mag_register(CfgRegAM::default() | CfgRegAM::SOFT_RST)?;
+        self.write_mag_register(CfgRegAM::default() | CfgRegAM::REBOOT)?;
+
+        delay.delay_us(BOOT_TIME_US);
+
+        // Rebooting only reloads the trimming parameters, so the accelerometer
+        // configuration registers need to be restored explicitly.
+        self.write_accel_register(CtrlReg1A::default())?;
+        self.write_accel_register(CtrlReg2A::default())?;
+        self.write_accel_register(CtrlReg3A::default())?;
+        self.write_accel_register(CtrlReg4A::default())?;
+        self.write_accel_register(CtrlReg5A::default())?;
+        self.write_accel_register(CtrlReg6A::default())?;
+        self.write_accel_register(ReferenceA::default())?;
+        self.write_accel_register(TempCfgRegA::default())?;
+        self.write_accel_register(FifoCtrlRegA::default())?;
+        self.write_accel_register(Int1CfgA::empty())?;
+        self.write_accel_register(Int1ThsA::default())?;
+        self.write_accel_register(Int1DurationA::default())?;
+        self.write_accel_register(Int2CfgA::empty())?;
+        self.write_accel_register(Int2ThsA::default())?;
+        self.write_accel_register(Int2DurationA::default())?;
+        self.write_accel_register(ClickCfgA::empty())?;
+        self.write_accel_register(ClickThsA::empty())?;
+        self.write_accel_register(TimeLimitA::empty())?;
+        self.write_accel_register(TimeLatencyA::empty())?;
+        self.write_accel_register(TimeWindowA::empty())?;
+        self.write_accel_register(ActThsA::default())?;
+        self.write_accel_register(ActDurA::default())?;
+
+        self.ctrl_reg1_a = CtrlReg1A::default();
+        self.ctrl_reg2_a = CtrlReg2A::default();
+        self.ctrl_reg3_a = CtrlReg3A::default();
+        self.ctrl_reg4_a = CtrlReg4A::default();
+        self.ctrl_reg5_a = CtrlReg5A::default();
+        self.ctrl_reg6_a = CtrlReg6A::default();
+        self.cfg_reg_a_m = CfgRegAM::default();
+        self.cfg_reg_b_m = CfgRegBM::default();
+        self.cfg_reg_c_m = CfgRegCM::default();
+        self.int_crtl_reg_m = IntCrtlRegM::default();
+        self.temp_cfg_reg_a = TempCfgRegA::default();
+        self.fifo_ctrl_reg_a = FifoCtrlRegA::default();
+        self.accel_odr = None;
+        self.mag_one_shot_triggered = false;
+
+        Ok(())
+    }
+}
+
+impl<SPI, CSXL, CSMAG, CommE, PinE, MODE> Lsm303agr<SpiInterface<SPI, CSXL, CSMAG>, MODE>
+where
+    SpiInterface<SPI, CSXL, CSMAG>:
+        ReadData<Error = Error<CommE, PinE>> + WriteData<Error = Error<CommE, PinE>>,
+{
+    /// Enable 3-wire SPI mode for the accelerometer.
+    ///
+    /// The LSM303AGR only provides a single bidirectional data line (SDA/SDI/SDO),
+    /// which is always used in 3-wire mode by the magnetometer, while the accelerometer
+    /// only answers reads once 3-wire mode is enabled. The bus must release the shared
+    /// data line after the address byte (turnaround) so that the device can drive it,
+    /// e.g. by connecting MOSI to the data line through a resistor and MISO directly.
+    pub fn enable_spi_3_wire(&mut self) -> Result<(), Error<CommE, PinE>> {
+        let reg4 = self.ctrl_reg4_a | CtrlReg4A::SPI_ENABLE;
+        self.write_accel_register(reg4)?;
+        self.ctrl_reg4_a = reg4;
+
+        Ok(())
+    }
+
+    /// Disable the magnetometer I2C interface.
+    ///
+    /// This prevents glitches on the shared SPI pins from being interpreted
+    /// as I2C traffic. The accelerometer has no I2C disable bit.
+    pub fn disable_i2c(&mut self) -> Result<(), Error<CommE, PinE>> {
+        let regc = self.cfg_reg_c_m | CfgRegCM::I2C_DIS;
+        self.write_mag_register(regc)?;
+        self.cfg_reg_c_m = regc;
+
+        Ok(())
+    }
+}
+
 impl<DI, CommE, PinE, MODE> Lsm303agr<DI, MODE>
 where
     DI: ReadData<Error = Error<CommE, PinE>> + WriteData<Error = Error<CommE, PinE>>,
@@ -81,7 +359,7 @@ where
     #[inline]
     fn acc_enable_bdu(&mut self) -> Result<(), Error<CommE, PinE>> {
         let reg4 = self.ctrl_reg4_a | CtrlReg4A::BDU;
-        self.iface.write_accel_register(reg4)?;
+        self.write_accel_register(reg4)?;
         self.ctrl_reg4_a = reg4;
 
         Ok(())
@@ -93,7 +371,7 @@ where
         self.acc_enable_bdu()?;
 
         let temp_cfg_reg = self.temp_cfg_reg_a | TempCfgRegA::TEMP_EN;
-        self.iface.write_accel_register(temp_cfg_reg)?;
+        self.write_accel_register(temp_cfg_reg)?;
         self.temp_cfg_reg_a = temp_cfg_reg;
 
         Ok(())
@@ -103,65 +381,506 @@ where
     #[inline]
     fn mag_enable_bdu(&mut self) -> Result<(), Error<CommE, PinE>> {
         let regc = self.cfg_reg_c_m | CfgRegCM::BDU;
-        self.iface.write_mag_register(regc)?;
+        self.write_mag_register(regc)?;
         self.cfg_reg_c_m = regc;
 
         Ok(())
     }
 
+    /// Read back all control registers from the device.
+    ///
+    /// This updates the cached register values, e.g. after the device has been
+    /// reset or power-cycled independently of the microcontroller.
+    ///
+    /// Note that the magnetometer mode type is not changed.
+    pub fn sync_from_device(&mut self) -> Result<(), Error<CommE, PinE>> {
+        self.ctrl_reg1_a = self.iface.read_accel_register::<CtrlReg1A>()?;
+        self.ctrl_reg2_a = self.iface.read_accel_register::<CtrlReg2A>()?;
+        self.ctrl_reg3_a = self.iface.read_accel_register::<CtrlReg3A>()?;
+        self.ctrl_reg4_a = self.iface.read_accel_register::<CtrlReg4A>()?;
+        self.ctrl_reg5_a = self.iface.read_accel_register::<CtrlReg5A>()?;
+        self.ctrl_reg6_a = self.iface.read_accel_register::<CtrlReg6A>()?;
+        self.temp_cfg_reg_a = self.iface.read_accel_register::<TempCfgRegA>()?;
+        self.fifo_ctrl_reg_a = self.iface.read_accel_register::<FifoCtrlRegA>()?;
+        self.cfg_reg_a_m = self.iface.read_mag_register::<CfgRegAM>()?;
+        self.cfg_reg_b_m = self.iface.read_mag_register::<CfgRegBM>()?;
+        self.cfg_reg_c_m = self.iface.read_mag_register::<CfgRegCM>()?;
+        self.int_crtl_reg_m = self.iface.read_mag_register::<IntCrtlRegM>()?;
+        self.accel_odr = self.ctrl_reg1_a.odr();
+
+        Ok(())
+    }
+
+    /// Enable or disable verification of register writes.
+    ///
+    /// When enabled, each control register is read back after writing it and
+    /// `Error::WriteVerification` is returned if the contents do not match, e.g.
+    /// due to bus corruption in noisy environments. This doubles the number of
+    /// transactions needed for configuration changes.
+    ///
+    /// The following bits are not verified, since they are changed by the device itself:
+    /// - `BOOT` in `CTRL_REG5_A`
+    /// - `REBOOT` and `SOFT_RST` in `CFG_REG_A_M`
+    /// - `MD` in `CFG_REG_A_M` when starting a single measurement, since the
+    ///   magnetometer returns to idle mode after the measurement
+    pub fn set_write_verification(&mut self, enabled: bool) {
+        self.verify_writes = enabled;
+    }
+
     /// Set the accelerometer FIFO mode and full threshold.
     ///
     /// The threshold is clamped to \[0, 31\].
     pub fn acc_set_fifo_mode(&mut self, mode: FifoMode, fth: u8) -> Result<(), Error<CommE, PinE>> {
         let mut reg5 = self.ctrl_reg5_a;
         reg5.set(CtrlReg5A::FIFO_EN, mode != FifoMode::Bypass);
-        self.iface.write_accel_register(reg5)?;
+        self.write_accel_register(reg5)?;
         self.ctrl_reg5_a = reg5;
 
         let fifo_ctrl = self
             .fifo_ctrl_reg_a
             .with_mode(mode)
             .with_full_threshold(fth);
-        self.iface.write_accel_register(fifo_ctrl)?;
+        self.write_accel_register(fifo_ctrl)?;
         self.fifo_ctrl_reg_a = fifo_ctrl;
 
         Ok(())
     }
 
-    /// Enable accelerometer interrupt.
-    pub fn acc_enable_interrupt(&mut self, interrupt: Interrupt) -> Result<(), Error<CommE, PinE>> {
-        let reg3 = self.ctrl_reg3_a.with_interrupt(interrupt);
-        self.iface.write_accel_register(reg3)?;
-        self.ctrl_reg3_a = reg3;
+    /// Set the accelerometer FIFO watermark threshold.
+    ///
+    /// The FIFO mode is left unchanged.
+    ///
+    /// Returns `Error::InvalidInputData` if the threshold exceeds 31.
+    pub fn set_fifo_watermark(&mut self, n: u8) -> Result<(), Error<CommE, PinE>> {
+        if n > FifoCtrlRegA::FTH.bits() {
+            return Err(Error::InvalidInputData);
+        }
+
+        let fifo_ctrl = self.fifo_ctrl_reg_a.with_full_threshold(n);
+        self.write_accel_register(fifo_ctrl)?;
+        self.fifo_ctrl_reg_a = fifo_ctrl;
 
         Ok(())
     }
 
+    /// Set the accelerometer FIFO trigger source.
+    ///
+    /// This selects which interrupt triggers the switch from stream to FIFO mode
+    /// when using [`FifoMode::StreamToFifo`].
+    pub fn acc_set_fifo_trigger(&mut self, line: InterruptLine) -> Result<(), Error<CommE, PinE>> {
+        let mut fifo_ctrl = self.fifo_ctrl_reg_a;
+        fifo_ctrl.set(FifoCtrlRegA::TR, line == InterruptLine::Int2);
+        self.write_accel_register(fifo_ctrl)?;
+        self.fifo_ctrl_reg_a = fifo_ctrl;
+
+        Ok(())
+    }
+
+    /// Configure the accelerometer FIFO to capture samples around a trigger event.
+    ///
+    /// This empties the FIFO, sets the watermark threshold to `pre_samples`, selects
+    /// `trigger` as the FIFO trigger source and enables [`FifoMode::StreamToFifo`].
+    /// The FIFO then keeps the latest samples until the interrupt on the `trigger`
+    /// line fires, after which it fills up the remaining slots and stops. The interrupt
+    /// itself, e.g. a click or motion interrupt, has to be configured and routed to the
+    /// `trigger` line separately.
+    ///
+    /// This waits until `pre_samples` samples were collected at the current output data
+    /// rate, so that the window before the trigger is available. Afterwards, the
+    /// watermark flag in the FIFO status indicates that at least `pre_samples` samples
+    /// are buffered.
+    ///
+    /// Returns `Error::InvalidInputData` if `pre_samples` exceeds 31 or if the
+    /// accelerometer is powered down.
+    pub fn acc_configure_trigger_capture<D: DelayUs<u32>>(
+        &mut self,
+        pre_samples: u8,
+        trigger: InterruptLine,
+        delay: &mut D,
+    ) -> Result<(), Error<CommE, PinE>> {
+        if pre_samples > FifoCtrlRegA::FTH.bits() {
+            return Err(Error::InvalidInputData);
+        }
+        let odr = self.accel_odr.ok_or(Error::InvalidInputData)?;
+
+        let reg5 = self.ctrl_reg5_a.union(CtrlReg5A::FIFO_EN);
+        self.write_accel_register(reg5)?;
+        self.ctrl_reg5_a = reg5;
+
+        // Going through bypass mode empties the FIFO.
+        let mut fifo_ctrl = self
+            .fifo_ctrl_reg_a
+            .with_mode(FifoMode::Bypass)
+            .with_full_threshold(pre_samples);
+        fifo_ctrl.set(FifoCtrlRegA::TR, trigger == InterruptLine::Int2);
+        self.write_accel_register(fifo_ctrl)?;
+        self.fifo_ctrl_reg_a = fifo_ctrl;
+
+        let fifo_ctrl = fifo_ctrl.with_mode(FifoMode::StreamToFifo);
+        self.write_accel_register(fifo_ctrl)?;
+        self.fifo_ctrl_reg_a = fifo_ctrl;
+
+        // Wait for the pre-trigger samples, 1/ODR in µs each.
+        delay.delay_us(u32::from(pre_samples) * odr.turn_on_time_us_frac_1());
+
+        Ok(())
+    }
+
+    /// Enable accelerometer interrupt.
+    ///
+    /// The interrupt is given as an [`InterruptLine`] and [`InterruptSource`] pair,
+    /// e.g. `(InterruptLine::Int2, InterruptSource::Aoi1)`. An [`Interrupt`] can
+    /// also be passed to route the interrupt to the INT1 pin.
+    ///
+    /// Returns `Error::InvalidInputData` if the interrupt source cannot be routed
+    /// to the given pin.
+    pub fn acc_enable_interrupt<I>(&mut self, interrupt: I) -> Result<(), Error<CommE, PinE>>
+    where
+        I: Into<(InterruptLine, InterruptSource)>,
+    {
+        self.acc_set_interrupt(interrupt.into(), true)
+    }
+
     /// Disable accelerometer interrupt.
-    pub fn acc_disable_interrupt(
+    ///
+    /// See [`acc_enable_interrupt`](Self::acc_enable_interrupt).
+    pub fn acc_disable_interrupt<I>(&mut self, interrupt: I) -> Result<(), Error<CommE, PinE>>
+    where
+        I: Into<(InterruptLine, InterruptSource)>,
+    {
+        self.acc_set_interrupt(interrupt.into(), false)
+    }
+
+    fn acc_set_interrupt(
         &mut self,
-        interrupt: Interrupt,
+        (line, source): (InterruptLine, InterruptSource),
+        enable: bool,
     ) -> Result<(), Error<CommE, PinE>> {
-        let reg3 = self.ctrl_reg3_a.without_interrupt(interrupt);
-        self.iface.write_accel_register(reg3)?;
+        match line {
+            InterruptLine::Int1 => {
+                let flag = CtrlReg3A::interrupt_flag(source).ok_or(Error::InvalidInputData)?;
+                let mut reg3 = self.ctrl_reg3_a;
+                reg3.set(flag, enable);
+                self.write_accel_register(reg3)?;
+                self.ctrl_reg3_a = reg3;
+            }
+            InterruptLine::Int2 => {
+                let flag = CtrlReg6A::interrupt_flag(source).ok_or(Error::InvalidInputData)?;
+                let mut reg6 = self.ctrl_reg6_a;
+                reg6.set(flag, enable);
+                self.write_accel_register(reg6)?;
+                self.ctrl_reg6_a = reg6;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Enable or disable the FIFO watermark and overrun interrupts in a single write.
+    ///
+    /// Other interrupts are left unchanged.
+    ///
+    /// Returns `Error::InvalidInputData` if `line` is [`InterruptLine::Int2`], since the
+    /// FIFO interrupts can only be routed to the INT1 pin.
+    pub fn acc_enable_fifo_interrupts(
+        &mut self,
+        watermark: bool,
+        overrun: bool,
+        line: InterruptLine,
+    ) -> Result<(), Error<CommE, PinE>> {
+        if line != InterruptLine::Int1 {
+            return Err(Error::InvalidInputData);
+        }
+
+        let mut reg3 = self.ctrl_reg3_a;
+        reg3.set(CtrlReg3A::I1_WTM, watermark);
+        reg3.set(CtrlReg3A::I1_OVERRUN, overrun);
+        self.write_accel_register(reg3)?;
         self.ctrl_reg3_a = reg3;
 
         Ok(())
     }
 
+    /// Enable the accelerometer data-ready interrupt.
+    ///
+    /// This routes the data-ready signal to the given interrupt pin and configures
+    /// the interrupt pin polarity, which applies to both the INT1 and INT2 pins.
+    /// The interrupt pins are always push-pull outputs.
+    ///
+    /// Returns `Error::InvalidInputData` for [`InterruptLine::Int2`] since the
+    /// data-ready signal can only be routed to the INT1 pin.
+    pub fn acc_enable_data_ready_interrupt(
+        &mut self,
+        line: InterruptLine,
+        active_low: bool,
+    ) -> Result<(), Error<CommE, PinE>> {
+        if line != InterruptLine::Int1 {
+            return Err(Error::InvalidInputData);
+        }
+
+        let mut reg6 = self.ctrl_reg6_a;
+        reg6.set(CtrlReg6A::H_LACTIVE, active_low);
+        self.write_accel_register(reg6)?;
+        self.ctrl_reg6_a = reg6;
+
+        self.acc_enable_interrupt(Interrupt::DataReady1)
+    }
+
+    /// Configure the accelerometer interrupt generator.
+    ///
+    /// To route the interrupt to the INT1 pin, enable [`Interrupt::Aoi1`].
+    ///
+    /// Returns `Error::InvalidInputData` if the threshold or duration exceeds 127.
+    pub fn acc_configure_interrupt_generator(
+        &mut self,
+        config: AccelInterruptConfig,
+    ) -> Result<(), Error<CommE, PinE>> {
+        if config.threshold > Int1ThsA::THS.bits() || config.duration > Int1DurationA::D.bits() {
+            return Err(Error::InvalidInputData);
+        }
+
+        self.write_accel_register(Int1ThsA::from_bits_truncate(config.threshold))?;
+        self.write_accel_register(Int1DurationA::from_bits_truncate(config.duration))?;
+        self.write_accel_register(Int1CfgA::with_config(config))
+    }
+
+    /// Configure accelerometer click detection.
+    ///
+    /// The threshold and time values are converted to LSB using the current
+    /// scale and output data rate, see [`AccelClickConfig`]. Set the scale and
+    /// output data rate before configuring click detection.
+    ///
+    /// To route the click interrupt to the INT1 pin, enable [`Interrupt::Click`].
+    ///
+    /// Returns `Error::InvalidInputData` if the accelerometer is powered down or
+    /// if any converted value is out of range.
+    pub fn acc_configure_click(
+        &mut self,
+        config: AccelClickConfig,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let odr_hz = u64::from(u16::from(self.accel_odr.ok_or(Error::InvalidInputData)?));
+        let scale_g = u64::from(u16::from(self.get_accel_scale()));
+
+        let threshold =
+            u64::try_from(i32::from(config.threshold)).map_err(|_| Error::InvalidInputData)?;
+        // 1 LSB = full scale / 128
+        let threshold = threshold * 128 / (scale_g * 1000);
+        // 1 LSB = 1/ODR
+        let to_lsb = |us: u32| u64::from(us) * odr_hz / 1_000_000;
+        let time_limit = to_lsb(config.time_limit_us);
+        let time_latency = to_lsb(config.time_latency_us);
+        let time_window = to_lsb(config.time_window_us);
+
+        if threshold > u64::from(ClickThsA::THS.bits())
+            || time_limit > u64::from(TimeLimitA::TLI.bits())
+            || time_latency + time_window > u64::from(TimeWindowA::TW.bits())
+        {
+            return Err(Error::InvalidInputData);
+        }
+
+        let mut ths = ClickThsA::from_bits_truncate(threshold as u8);
+        ths.set(ClickThsA::LIR_CLICK, config.latched);
+        self.write_accel_register(ths)?;
+        self.write_accel_register(TimeLimitA::from_bits_truncate(time_limit as u8))?;
+        self.write_accel_register(TimeLatencyA::from_bits_truncate(time_latency as u8))?;
+        self.write_accel_register(TimeWindowA::from_bits_truncate(time_window as u8))?;
+        self.write_accel_register(ClickCfgA::with_config(config))
+    }
+
+    /// Configure wake on motion.
+    ///
+    /// This switches the accelerometer to low-power mode with an output data rate of
+    /// 10 Hz and enables the activity detection of the sleep-to-wake function. The
+    /// activity status is routed to the INT2 pin, which is asserted while the
+    /// acceleration stays below `threshold`, i.e. the MCU can sleep until the pin
+    /// is deasserted.
+    ///
+    /// **This overwrites the previously configured output data rate and mode.** The
+    /// sleep-to-wake function only switches between low-power mode at 10 Hz and the
+    /// configured mode and output data rate, which are the same here, so waking up
+    /// does not restore the previous ones. The previous output data rate and mode
+    /// are returned, so they can be restored after waking up using
+    /// [`set_accel_odr()`](Self::set_accel_odr) and
+    /// [`set_accel_mode()`](Self::set_accel_mode).
+    ///
+    /// The `duration` is given in steps of 8/ODR, i.e. 0.8 s, and the pin is asserted
+    /// after (8 * `duration` + 1) / ODR without motion.
+    ///
+    /// Returns `Error::InvalidInputData` if the threshold exceeds 127 LSB, i.e. 2 *g*
+    /// at ±2*g*, 4 *g* at ±4*g*, 7.9 *g* at ±8*g* and 23.6 *g* at ±16*g*.
+    pub fn acc_configure_wake_on_motion<D: DelayUs<u32>>(
+        &mut self,
+        delay: &mut D,
+        threshold: MilliG,
+        duration: u8,
+    ) -> Result<(Option<AccelOutputDataRate>, AccelMode), Error<CommE, PinE>> {
+        // 1 LSB in mg
+        let lsb_mg = match self.get_accel_scale() {
+            AccelScale::G2 => 16,
+            AccelScale::G4 => 32,
+            AccelScale::G8 => 62,
+            AccelScale::G16 => 186,
+        };
+        let threshold = u32::try_from(threshold.0).map_err(|_| Error::InvalidInputData)? / lsb_mg;
+        if threshold > u32::from(ActThsA::ACTH.bits()) {
+            return Err(Error::InvalidInputData);
+        }
+
+        let previous = (self.get_accel_odr(), self.get_accel_mode());
+
+        self.set_accel_odr(delay, AccelOutputDataRate::Hz10)?;
+        self.set_accel_mode(delay, AccelMode::LowPower)?;
+
+        self.write_accel_register(ActThsA::from_bits_truncate(threshold as u8))?;
+        self.write_accel_register(ActDurA::from_bits_truncate(duration))?;
+
+        let reg6 = self.ctrl_reg6_a | CtrlReg6A::P2_ACT;
+        self.write_accel_register(reg6)?;
+        self.ctrl_reg6_a = reg6;
+
+        Ok(previous)
+    }
+
+    /// Get the accelerometer interrupt generator source.
+    ///
+    /// Reading the interrupt source clears a latched interrupt.
+    pub fn acc_interrupt_source(&mut self) -> Result<AccelInterruptSource, Error<CommE, PinE>> {
+        self.iface
+            .read_accel_register::<Int1SrcA>()
+            .map(AccelInterruptSource::new)
+    }
+
+    /// Get the sources of both accelerometer interrupt generators.
+    ///
+    /// Returns the sources of interrupt generator 1 and 2, in that order. Reading
+    /// the interrupt sources clears latched interrupts, which also deasserts the
+    /// corresponding interrupt pins.
+    pub fn acc_interrupt_sources(
+        &mut self,
+    ) -> Result<(AccelInterruptSource, AccelInterruptSource), Error<CommE, PinE>> {
+        let int1 = self.iface.read_accel_register::<Int1SrcA>()?;
+        let int2 = self.iface.read_accel_register::<Int2SrcA>()?;
+
+        Ok((
+            AccelInterruptSource::new(int1),
+            AccelInterruptSource::new(int2),
+        ))
+    }
+
     /// Configure the DRDY pin as a digital output.
-    pub fn mag_enable_int(&mut self) -> Result<(), Error<CommE, PinE>> {
+    ///
+    /// This sets `INT_MAG` in `CFG_REG_C_M`, which outputs the magnetometer data-ready
+    /// signal on the DRDY pin. It does not affect the threshold interrupt, see
+    /// [`mag_enable_threshold_interrupt()`](Lsm303agr::mag_enable_threshold_interrupt).
+    pub fn mag_enable_drdy_pin(&mut self) -> Result<(), Error<CommE, PinE>> {
         let regc = self.cfg_reg_c_m | CfgRegCM::INT_MAG;
-        self.iface.write_mag_register(regc)?;
+        self.write_mag_register(regc)?;
         self.cfg_reg_c_m = regc;
 
         Ok(())
     }
 
+    /// Configure the DRDY pin as a digital output.
+    #[deprecated(note = "use `mag_enable_drdy_pin()` instead")]
+    pub fn mag_enable_int(&mut self) -> Result<(), Error<CommE, PinE>> {
+        self.mag_enable_drdy_pin()
+    }
+
+    /// Enable or disable the magnetometer threshold interrupt.
+    ///
+    /// This sets `IEN` in `INT_CRTL_REG_M`, which enables the interrupt generation,
+    /// and `INT_MAG_PIN` in `CFG_REG_C_M`, which outputs the interrupt on the INT_MAG
+    /// pin. The axes, latching and polarity are configured with
+    /// [`mag_configure_interrupt()`](Lsm303agr::mag_configure_interrupt).
+    ///
+    /// The data-ready signal is output on the separate DRDY pin, see
+    /// [`mag_enable_drdy_pin()`](Lsm303agr::mag_enable_drdy_pin).
+    pub fn mag_enable_threshold_interrupt(
+        &mut self,
+        enabled: bool,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let mut int_crtl = self.int_crtl_reg_m;
+        int_crtl.set(IntCrtlRegM::IEN, enabled);
+        self.write_mag_register(int_crtl)?;
+        self.int_crtl_reg_m = int_crtl;
+
+        self.mag_route_interrupt_to_pin(enabled)
+    }
+
+    /// Route the magnetometer interrupt to the INT_MAG pin.
+    ///
+    /// This sets `INT_MAG_PIN` in `CFG_REG_C_M`. The data-ready signal is output on the
+    /// separate DRDY pin, see [`mag_enable_drdy_pin()`](Lsm303agr::mag_enable_drdy_pin).
+    ///
+    /// The interrupt itself is configured with [`mag_configure_interrupt()`](Lsm303agr::mag_configure_interrupt).
+    /// If it is not routed to the pin, it can still be read using
+    /// [`mag_interrupt_source()`](Lsm303agr::mag_interrupt_source).
+    pub fn mag_route_interrupt_to_pin(&mut self, enabled: bool) -> Result<(), Error<CommE, PinE>> {
+        let mut regc = self.cfg_reg_c_m;
+        regc.set(CfgRegCM::INT_MAG_PIN, enabled);
+        self.write_mag_register(regc)?;
+        self.cfg_reg_c_m = regc;
+
+        Ok(())
+    }
+
+    /// Configure magnetometer interrupt.
+    ///
+    /// This selects which axes generate the interrupt, whether it is latched
+    /// or pulsed, its polarity and whether it is enabled at all.
+    pub fn mag_configure_interrupt(
+        &mut self,
+        config: MagInterruptConfig,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let int_crtl = IntCrtlRegM::with_config(config);
+        self.write_mag_register(int_crtl)?;
+        self.int_crtl_reg_m = int_crtl;
+
+        Ok(())
+    }
+
+    /// Set the magnetometer interrupt polarity.
+    ///
+    /// The rest of the interrupt configuration is left unchanged.
+    pub fn mag_set_interrupt_polarity(
+        &mut self,
+        active_high: bool,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let mut int_crtl = self.int_crtl_reg_m;
+        int_crtl.set(IntCrtlRegM::IEA, active_high);
+        self.write_mag_register(int_crtl)?;
+        self.int_crtl_reg_m = int_crtl;
+
+        Ok(())
+    }
+
+    /// Set whether the magnetometer interrupt is latched or pulsed.
+    ///
+    /// A latched interrupt stays active until the interrupt source is read using
+    /// [`mag_interrupt_source()`](Lsm303agr::mag_interrupt_source). The rest of the
+    /// interrupt configuration is left unchanged.
+    pub fn mag_set_interrupt_latched(&mut self, latched: bool) -> Result<(), Error<CommE, PinE>> {
+        let mut int_crtl = self.int_crtl_reg_m;
+        int_crtl.set(IntCrtlRegM::IEL, latched);
+        self.write_mag_register(int_crtl)?;
+        self.int_crtl_reg_m = int_crtl;
+
+        Ok(())
+    }
+
+    /// Get the magnetometer interrupt source.
+    ///
+    /// Reading the interrupt source clears a latched interrupt.
+    pub fn mag_interrupt_source(&mut self) -> Result<MagInterruptSource, Error<CommE, PinE>> {
+        self.iface
+            .read_mag_register::<IntSourceRegM>()
+            .map(MagInterruptSource::new)
+    }
+
     /// Enable magnetometer low-pass filter.
     pub fn mag_enable_low_pass_filter(&mut self) -> Result<(), Error<CommE, PinE>> {
         let regb = self.cfg_reg_b_m.union(CfgRegBM::LPF);
-        self.iface.write_mag_register(regb)?;
+        self.write_mag_register(regb)?;
         self.cfg_reg_b_m = regb;
 
         Ok(())
@@ -170,12 +889,50 @@ where
     /// Disable magnetometer low-pass filter.
     pub fn mag_disable_low_pass_filter(&mut self) -> Result<(), Error<CommE, PinE>> {
         let regb = self.cfg_reg_b_m.difference(CfgRegBM::LPF);
-        self.iface.write_mag_register(regb)?;
+        self.write_mag_register(regb)?;
         self.cfg_reg_b_m = regb;
 
         Ok(())
     }
 
+    /// Set the magnetometer set pulse frequency.
+    ///
+    /// If `periodic` is `true` (the default), the set pulse is applied every 63
+    /// output data rate cycles, which reduces offset drift. If it is `false`, the
+    /// set pulse is only applied on power-on after power-down, which saves power.
+    pub fn mag_set_pulse_frequency(&mut self, periodic: bool) -> Result<(), Error<CommE, PinE>> {
+        let mut regb = self.cfg_reg_b_m;
+        regb.set(CfgRegBM::SET_FREQ, !periodic);
+        self.write_mag_register(regb)?;
+        self.cfg_reg_b_m = regb;
+
+        Ok(())
+    }
+
+    /// Set the byte order of the magnetometer output registers.
+    ///
+    /// Magnetic field reads assemble the output registers according to the
+    /// selected byte order, so measured values do not change.
+    pub fn mag_set_big_endian(&mut self, big_endian: bool) -> Result<(), Error<CommE, PinE>> {
+        let mut regc = self.cfg_reg_c_m;
+        regc.set(CfgRegCM::BLE, big_endian);
+        self.write_mag_register(regc)?;
+        self.cfg_reg_c_m = regc;
+
+        Ok(())
+    }
+
+    /// Check whether the accelerometer has finished rebooting its memory content.
+    ///
+    /// The `BOOT` bit in `CTRL_REG5_A` is cleared by the device once the trimming
+    /// parameters have been reloaded, after which the registers hold valid values
+    /// again. This can be polled after [`reset()`](Lsm303agr::reset) instead of
+    /// relying only on the fixed boot delay.
+    pub fn acc_reboot_complete(&mut self) -> Result<bool, Error<CommE, PinE>> {
+        let reg5 = self.iface.read_accel_register::<CtrlReg5A>()?;
+        Ok(!reg5.contains(CtrlReg5A::BOOT))
+    }
+
     /// Accelerometer status
     pub fn accel_status(&mut self) -> Result<Status, Error<CommE, PinE>> {
         self.iface
@@ -184,8 +941,18 @@ where
     }
 
     /// Get measured acceleration.
+    ///
+    /// When the FIFO is enabled, this returns the oldest sample stored in the FIFO
+    /// and removes it from the FIFO. Use [`acc_peek_latest()`](Lsm303agr::acc_peek_latest)
+    /// to avoid accidentally draining the FIFO.
+    ///
+    /// Returns `Error::InvalidInputData` if the accelerometer is powered down.
     pub fn acceleration(&mut self) -> Result<Acceleration, Error<CommE, PinE>> {
-        let (x, y, z) = self.iface.read_accel_3_double_registers::<Acceleration>()?;
+        if self.get_accel_mode() == AccelMode::PowerDown {
+            return Err(Error::InvalidInputData);
+        }
+
+        let (x, y, z) = self.read_accel_xyz()?;
 
         Ok(Acceleration {
             x,
@@ -196,6 +963,129 @@ where
         })
     }
 
+    /// Get measured acceleration along a single axis in m*g* (milli-*g*).
+    ///
+    /// This only reads the two output registers of the given `axis`, which is
+    /// useful when only one axis is needed and reading all three axes would
+    /// needlessly increase bus traffic.
+    ///
+    /// Returns `Error::InvalidInputData` if the accelerometer is powered down.
+    pub fn acceleration_axis(&mut self, axis: Axis) -> Result<i32, Error<CommE, PinE>> {
+        let mode = self.get_accel_mode();
+        if mode == AccelMode::PowerDown {
+            return Err(Error::InvalidInputData);
+        }
+
+        let mut raw = match axis {
+            Axis::X => self.iface.read_accel_double_register::<OutXA>()?,
+            Axis::Y => self.iface.read_accel_double_register::<OutYA>()?,
+            Axis::Z => self.iface.read_accel_double_register::<OutZA>()?,
+        };
+        if self.ctrl_reg4_a.contains(CtrlReg4A::BLE) {
+            raw = raw.swap_bytes();
+        }
+
+        let acceleration = Acceleration {
+            x: raw,
+            ..Acceleration::zero(mode, self.get_accel_scale())
+        };
+        Ok(acceleration.x_mg())
+    }
+
+    /// Get measured acceleration together with the accelerometer status.
+    ///
+    /// The status is read immediately before the output registers, so it describes
+    /// the returned sample, e.g. whether it is new or whether previous samples were
+    /// overwritten. With block data update enabled (see [`init()`](Lsm303agr::init)),
+    /// the output registers cannot change between the two reads.
+    pub fn acceleration_with_status(
+        &mut self,
+    ) -> Result<(Acceleration, Status), Error<CommE, PinE>> {
+        let status = self.accel_status()?;
+        let acceleration = self.acceleration()?;
+
+        Ok((acceleration, status))
+    }
+
+    /// Get measured acceleration, checking that the sample was not torn.
+    ///
+    /// Without block data update, the output registers may be updated while they
+    /// are being read, so the returned axes may belong to different samples. This
+    /// reads `STATUS_REG_A` after the output registers: reading the output registers
+    /// clears the overrun bit, so if it is set again, the output registers were
+    /// overwritten during the read and the read is retried up to `retries` times.
+    /// An overrun which was already set before the read therefore does not hide a
+    /// torn sample. New data arriving after the read without overwriting the output
+    /// registers does not cause a retry.
+    ///
+    /// Returns `nb::Error::WouldBlock` if no clean sample could be read.
+    pub fn acceleration_checked(
+        &mut self,
+        retries: u8,
+    ) -> nb::Result<Acceleration, Error<CommE, PinE>> {
+        for _ in 0..=retries {
+            let acceleration = self.acceleration()?;
+            let status = self.accel_status()?;
+
+            if !status.xyz_overrun() {
+                return Ok(acceleration);
+            }
+        }
+
+        Err(nb::Error::WouldBlock)
+    }
+
+    /// Get the latest measured acceleration.
+    ///
+    /// Unlike [`acceleration()`](Lsm303agr::acceleration), this never reads from the
+    /// FIFO. Returns `Error::InvalidInputData` if the FIFO is enabled, since reading
+    /// the output registers would then remove the oldest sample from the FIFO, or
+    /// if the accelerometer is powered down.
+    pub fn acc_peek_latest(&mut self) -> Result<Acceleration, Error<CommE, PinE>> {
+        if self.ctrl_reg5_a.contains(CtrlReg5A::FIFO_EN) {
+            return Err(Error::InvalidInputData);
+        }
+
+        self.acceleration()
+    }
+
+    /// Get measured acceleration together with a timestamp.
+    ///
+    /// The `clock` is read immediately before the acceleration is read from the
+    /// device, so that the time skew between the timestamp and the measurement is
+    /// as small as possible.
+    pub fn acceleration_with_timestamp<T, F: FnMut() -> T>(
+        &mut self,
+        mut clock: F,
+    ) -> Result<(Acceleration, T), Error<CommE, PinE>> {
+        let timestamp = clock();
+        let acceleration = self.acceleration()?;
+
+        Ok((acceleration, timestamp))
+    }
+
+    /// Get the raw left-justified acceleration for the X-, Y- and Z-directions.
+    ///
+    /// Unlike [`acceleration()`](Lsm303agr::acceleration), no resolution or
+    /// scaling factor is applied.
+    pub fn acceleration_raw(&mut self) -> Result<(i16, i16, i16), Error<CommE, PinE>> {
+        let (x, y, z) = self.read_accel_xyz()?;
+
+        Ok((x as i16, y as i16, z as i16))
+    }
+
+    /// Get measured acceleration in m*g* (milli-*g*) for the X-, Y- and Z-directions.
+    pub fn acceleration_typed(&mut self) -> Result<(MilliG, MilliG, MilliG), Error<CommE, PinE>> {
+        self.acceleration()
+            .map(|acceleration| acceleration.xyz_milli_g())
+    }
+
+    /// Get measured acceleration in the given [`AccelUnit`] for the X-, Y- and Z-directions.
+    pub fn acceleration_in<U: AccelUnit>(&mut self) -> Result<(U, U, U), Error<CommE, PinE>> {
+        self.acceleration()
+            .map(|acceleration| acceleration.xyz_in())
+    }
+
     /// Magnetometer status
     pub fn mag_status(&mut self) -> Result<Status, Error<CommE, PinE>> {
         self.iface
@@ -203,6 +1093,11 @@ where
             .map(Status::new)
     }
 
+    /// Check whether new magnetometer data is available for all axes.
+    pub fn mag_data_ready(&mut self) -> Result<bool, Error<CommE, PinE>> {
+        Ok(self.mag_status()?.xyz_new_data())
+    }
+
     /// Get the accelerometer device ID.
     pub fn accelerometer_id(&mut self) -> Result<AccelerometerId, Error<CommE, PinE>> {
         self.iface.read_accel_register::<WhoAmIA>()
@@ -213,9 +1108,45 @@ where
         self.iface.read_mag_register::<WhoAmIM>()
     }
 
+    /// Get the accelerometer and magnetometer device IDs.
+    ///
+    /// This is useful to check that the device is present and correctly wired.
+    pub fn identity(&mut self) -> Result<(AccelerometerId, MagnetometerId), Error<CommE, PinE>> {
+        Ok((self.accelerometer_id()?, self.magnetometer_id()?))
+    }
+
+    /// Get the source of the temperature data.
+    ///
+    /// The temperature sensor is enabled by [`init`](Self::init).
+    pub fn acc_temperature_source(&self) -> TempSource {
+        if self.temp_cfg_reg_a.contains(TempCfgRegA::TEMP_EN) {
+            TempSource::Sensor
+        } else {
+            TempSource::Disabled
+        }
+    }
+
     /// Get measured temperature.
+    ///
+    /// The resolution of the measurement depends on the accelerometer mode,
+    /// see [`Temperature::resolution_bits`].
+    ///
+    /// Returns `Error::InvalidInputData` if the temperature sensor is disabled,
+    /// see [`acc_temperature_source`](Self::acc_temperature_source).
     pub fn temperature(&mut self) -> Result<Temperature, Error<CommE, PinE>> {
-        self.iface.read_accel_double_register::<Temperature>()
+        if self.acc_temperature_source() == TempSource::Disabled {
+            return Err(Error::InvalidInputData);
+        }
+
+        let mut raw = self.iface.read_accel_double_register::<Temperature>()?;
+        if self.ctrl_reg4_a.contains(CtrlReg4A::BLE) {
+            raw = raw.swap_bytes();
+        }
+
+        Ok(Temperature {
+            raw,
+            mode: self.get_accel_mode(),
+        })
     }
 
     /// Temperature sensor status
@@ -224,4 +1155,75 @@ where
             .read_accel_register::<StatusRegAuxA>()
             .map(TemperatureStatus::new)
     }
+
+    /// Read the accelerometer output registers.
+    ///
+    /// The interface always assembles the register pairs as little-endian, so the
+    /// values are swapped if big-endian output has been selected on the device
+    /// (`BLE` in `CTRL_REG4_A`), e.g. as read back by `sync_from_device`.
+    fn read_accel_xyz(&mut self) -> Result<(u16, u16, u16), Error<CommE, PinE>> {
+        let (x, y, z) = self.iface.read_accel_3_double_registers::<Acceleration>()?;
+
+        if self.ctrl_reg4_a.contains(CtrlReg4A::BLE) {
+            Ok((x.swap_bytes(), y.swap_bytes(), z.swap_bytes()))
+        } else {
+            Ok((x, y, z))
+        }
+    }
+
+    /// Read the magnetometer output registers.
+    ///
+    /// Like `read_accel_xyz`, this honours the
+    /// byte order selected on the device (`BLE` in `CFG_REG_C_M`).
+    pub(crate) fn read_mag_xyz(&mut self) -> Result<MagneticField, Error<CommE, PinE>> {
+        let field = self.iface.read_mag_3_double_registers::<MagneticField>()?;
+
+        if self.cfg_reg_c_m.contains(CfgRegCM::BLE) {
+            Ok(MagneticField {
+                x: field.x.swap_bytes(),
+                y: field.y.swap_bytes(),
+                z: field.z.swap_bytes(),
+            })
+        } else {
+            Ok(field)
+        }
+    }
+
+    /// Write an accelerometer register and read it back if write verification is enabled.
+    pub(crate) fn write_accel_register<R>(&mut self, reg: R) -> Result<(), Error<CommE, PinE>>
+    where
+        R: RegWrite<Output = R>,
+    {
+        let data = reg.data();
+        self.iface.write_accel_register(reg)?;
+
+        if self.verify_writes {
+            let mask = !accel_unverified_bits(R::ADDR);
+            let read_back = self.iface.read_accel_register::<R>()?.data();
+            if read_back & mask != data & mask {
+                return Err(Error::WriteVerification);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write a magnetometer register and read it back if write verification is enabled.
+    pub(crate) fn write_mag_register<R>(&mut self, reg: R) -> Result<(), Error<CommE, PinE>>
+    where
+        R: RegWrite<Output = R>,
+    {
+        let data = reg.data();
+        self.iface.write_mag_register(reg)?;
+
+        if self.verify_writes {
+            let mask = !mag_unverified_bits(R::ADDR, data);
+            let read_back = self.iface.read_mag_register::<R>()?.data();
+            if read_back & mask != data & mask {
+                return Err(Error::WriteVerification);
+            }
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,196 @@
+use crate::{
+    interface::{ReadData, WriteData},
+    mode,
+    register_address::{FifoCtrlRegA, FifoSrcRegA},
+    Acceleration, Error, FifoMode, Lsm303agr, MagneticField,
+};
+
+/// Number of samples the accelerometer FIFO can hold.
+const FIFO_SIZE: usize = 32;
+
+impl<DI, CommE, PinE, MODE> Lsm303agr<DI, MODE>
+where
+    DI: ReadData<Error = Error<CommE, PinE>> + WriteData<Error = Error<CommE, PinE>>,
+{
+    /// Iterate over the acceleration samples stored in the FIFO.
+    ///
+    /// Samples are read lazily until the FIFO is empty. Each sample is scaled
+    /// using the current accelerometer mode and scale.
+    ///
+    /// Dropping the iterator early leaves the remaining samples in the FIFO.
+    pub fn acc_fifo_iter(&mut self) -> FifoIter<'_, DI, MODE> {
+        FifoIter {
+            dev: self,
+            done: false,
+        }
+    }
+
+    /// Drain the accelerometer FIFO in stream mode.
+    ///
+    /// This reads the number of unread samples from `FIFO_SRC_REG_A`, reads them one
+    /// at a time, each in a separate transfer, and passes each one to `callback`. If the FIFO overran, i.e. samples were
+    /// overwritten before they could be read, the FIFO is reset by switching it to
+    /// bypass mode and back to stream mode after draining, which clears the overrun flag.
+    ///
+    /// The FIFO must be configured in stream mode first using
+    /// [`acc_set_fifo_mode()`](Lsm303agr::acc_set_fifo_mode) with [`FifoMode::Stream`],
+    /// otherwise `Error::InvalidInputData` is returned.
+    ///
+    /// The watermark should be chosen so that the FIFO is drained well before it is full.
+    /// Once the watermark interrupt fires, there are `(32 - watermark) / ODR` seconds left
+    /// until samples are lost, e.g. with a watermark of 16 at 100 Hz the FIFO has to be
+    /// drained within 160 ms. A watermark of about half the FIFO size is a good default.
+    pub fn acc_stream_drain<F>(
+        &mut self,
+        mut callback: F,
+    ) -> Result<StreamDrain, Error<CommE, PinE>>
+    where
+        F: FnMut(Acceleration),
+    {
+        if self.fifo_ctrl_reg_a.intersection(FifoCtrlRegA::FM) != FifoCtrlRegA::FM1 {
+            return Err(Error::InvalidInputData);
+        }
+
+        let fifo_src = self.iface.read_accel_register::<FifoSrcRegA>()?;
+        let overrun = fifo_src.contains(FifoSrcRegA::OVRN_FIFO);
+        let samples = sample_count(fifo_src);
+
+        for _ in 0..samples {
+            callback(self.acceleration()?);
+        }
+
+        if overrun {
+            let fifo_ctrl = self.fifo_ctrl_reg_a;
+            self.write_accel_register(fifo_ctrl.with_mode(FifoMode::Bypass))?;
+            self.write_accel_register(fifo_ctrl)?;
+        }
+
+        Ok(StreamDrain { samples, overrun })
+    }
+
+    /// Check whether the number of samples stored in the accelerometer FIFO has
+    /// reached the watermark set with [`acc_set_fifo_mode()`](Lsm303agr::acc_set_fifo_mode).
+    pub fn acc_fifo_watermark_reached(&mut self) -> Result<bool, Error<CommE, PinE>> {
+        let fifo_src = self.iface.read_accel_register::<FifoSrcRegA>()?;
+        Ok(fifo_src.contains(FifoSrcRegA::WTM))
+    }
+
+    /// Get the number of unread samples stored in the accelerometer FIFO.
+    ///
+    /// If the FIFO overran, it is full, so 32 is returned.
+    pub fn acc_fifo_sample_count(&mut self) -> Result<usize, Error<CommE, PinE>> {
+        let fifo_src = self.iface.read_accel_register::<FifoSrcRegA>()?;
+        Ok(sample_count(fifo_src))
+    }
+}
+
+/// Get the number of unread samples from `FIFO_SRC_REG_A`.
+fn sample_count(fifo_src: FifoSrcRegA) -> usize {
+    if fifo_src.contains(FifoSrcRegA::OVRN_FIFO) {
+        FIFO_SIZE
+    } else if fifo_src.contains(FifoSrcRegA::EMPTY) {
+        0
+    } else {
+        usize::from(fifo_src.intersection(FifoSrcRegA::FSS).bits())
+    }
+}
+
+/// Result of draining the accelerometer FIFO in stream mode.
+///
+/// See [`Lsm303agr::acc_stream_drain`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StreamDrain {
+    /// Number of samples passed to the callback.
+    pub samples: usize,
+    /// The FIFO overran and was reset, i.e. samples were lost before this drain.
+    pub overrun: bool,
+}
+
+/// Iterator over the acceleration samples stored in the FIFO.
+///
+/// See [`Lsm303agr::acc_fifo_iter`].
+#[derive(Debug)]
+pub struct FifoIter<'a, DI, MODE> {
+    dev: &'a mut Lsm303agr<DI, MODE>,
+    done: bool,
+}
+
+impl<'a, DI, CommE, PinE, MODE> Iterator for FifoIter<'a, DI, MODE>
+where
+    DI: ReadData<Error = Error<CommE, PinE>> + WriteData<Error = Error<CommE, PinE>>,
+{
+    type Item = Result<Acceleration, Error<CommE, PinE>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let fifo_src = match self.dev.iface.read_accel_register::<FifoSrcRegA>() {
+            Ok(fifo_src) => fifo_src,
+            Err(e) => {
+                self.done = true;
+                return Some(Err(e));
+            }
+        };
+
+        if fifo_src.contains(FifoSrcRegA::EMPTY) {
+            self.done = true;
+            return None;
+        }
+
+        let acceleration = self.dev.acceleration();
+        if acceleration.is_err() {
+            self.done = true;
+        }
+
+        Some(acceleration)
+    }
+}
+
+impl<DI, CommE, PinE> Lsm303agr<DI, mode::MagContinuous>
+where
+    DI: ReadData<Error = Error<CommE, PinE>> + WriteData<Error = Error<CommE, PinE>>,
+{
+    /// Iterate over the acceleration samples stored in the FIFO, each tagged with
+    /// the latest magnetic field.
+    ///
+    /// The magnetic field is read once before draining the FIFO, so all acceleration
+    /// samples of one drain are tagged with the same magnetic field. The FIFO holds
+    /// up to 32 samples, i.e. the oldest acceleration sample may be up to 32/ODR older
+    /// than the magnetic field. To keep this skew small, drain the FIFO at least as
+    /// often as the magnetometer output data rate, e.g. using a watermark of
+    /// accelerometer ODR / magnetometer ODR samples.
+    pub fn fifo_samples(&mut self) -> Result<Samples<'_, DI>, Error<CommE, PinE>> {
+        let magnetic_field = self.magnetic_field()?;
+
+        Ok(Samples {
+            iter: self.acc_fifo_iter(),
+            magnetic_field,
+        })
+    }
+}
+
+/// Iterator over the acceleration samples stored in the FIFO, each tagged with
+/// the latest magnetic field.
+///
+/// See [`Lsm303agr::fifo_samples`].
+#[derive(Debug)]
+pub struct Samples<'a, DI> {
+    iter: FifoIter<'a, DI, mode::MagContinuous>,
+    magnetic_field: MagneticField,
+}
+
+impl<'a, DI, CommE, PinE> Iterator for Samples<'a, DI>
+where
+    DI: ReadData<Error = Error<CommE, PinE>> + WriteData<Error = Error<CommE, PinE>>,
+{
+    type Item = Result<(Acceleration, MagneticField), Error<CommE, PinE>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let magnetic_field = self.magnetic_field;
+        self.iter
+            .next()
+            .map(|acceleration| acceleration.map(|acceleration| (acceleration, magnetic_field)))
+    }
+}
@@ -10,6 +10,11 @@ use crate::{
     register_address::{RegRead, RegWrite},
     Error,
 };
+#[cfg(feature = "fake-interface")]
+use crate::{
+    register_address::{CfgRegAM, CtrlReg1A, WhoAmIA, WhoAmIM},
+    Acceleration, MagneticField,
+};
 
 pub(crate) const ACCEL_ADDR: u8 = 0b001_1001;
 pub(crate) const MAG_ADDR: u8 = 0b001_1110;
@@ -20,6 +25,18 @@ pub struct I2cInterface<I2C> {
     pub(crate) i2c: I2C,
 }
 
+impl<I2C> I2cInterface<I2C> {
+    /// Create a new I2C interface, e.g. for use with [`DynInterface`].
+    pub fn new(i2c: I2C) -> Self {
+        Self { i2c }
+    }
+
+    /// Destroy the interface, return I2C bus.
+    pub fn destroy(self) -> I2C {
+        self.i2c
+    }
+}
+
 /// SPI interface
 #[derive(Debug)]
 pub struct SpiInterface<SPI, CSXL, CSMAG> {
@@ -28,6 +45,25 @@ pub struct SpiInterface<SPI, CSXL, CSMAG> {
     pub(crate) cs_mag: CSMAG,
 }
 
+impl<SPI, CSXL, CSMAG> SpiInterface<SPI, CSXL, CSMAG> {
+    /// Create a new SPI interface, e.g. for use with [`DynInterface`].
+    pub fn new(spi: SPI, chip_select_accel: CSXL, chip_select_mag: CSMAG) -> Self {
+        Self {
+            spi,
+            cs_xl: chip_select_accel,
+            cs_mag: chip_select_mag,
+        }
+    }
+
+    /// Destroy the interface, return SPI bus instance and chip select pins.
+    pub fn destroy(self) -> (SPI, CSXL, CSMAG) {
+        (self.spi, self.cs_xl, self.cs_mag)
+    }
+}
+
+/// Maximum number of consecutive registers read in a single transfer.
+pub(crate) const MAX_READ_LEN: usize = 6;
+
 /// Write data
 pub trait WriteData: private::Sealed {
     /// Error type
@@ -45,13 +81,11 @@ where
     type Error = Error<E, ()>;
 
     fn write_accel_register<R: RegWrite>(&mut self, reg: R) -> Result<(), Self::Error> {
-        let payload: [u8; 2] = [R::ADDR, reg.data()];
-        self.i2c.write(ACCEL_ADDR, &payload).map_err(Error::Comm)
+        self.write_byte(ACCEL_ADDR, R::ADDR, reg.data())
     }
 
     fn write_mag_register<R: RegWrite>(&mut self, reg: R) -> Result<(), Self::Error> {
-        let payload: [u8; 2] = [R::ADDR, reg.data()];
-        self.i2c.write(MAG_ADDR, &payload).map_err(Error::Comm)
+        self.write_byte(MAG_ADDR, R::ADDR, reg.data())
     }
 }
 
@@ -64,29 +98,19 @@ where
     type Error = Error<CommE, PinE>;
 
     fn write_accel_register<R: RegWrite>(&mut self, reg: R) -> Result<(), Self::Error> {
-        self.cs_xl.set_low().map_err(Error::Pin)?;
-
-        // note that multiple byte writing needs to set the MS bit
-        let payload: [u8; 2] = [R::ADDR, reg.data()];
-        let result = self.spi.write(&payload).map_err(Error::Comm);
-
-        self.cs_xl.set_high().map_err(Error::Pin)?;
-        result
+        Self::write_byte(&mut self.spi, &mut self.cs_xl, R::ADDR, reg.data())
     }
 
     fn write_mag_register<R: RegWrite>(&mut self, reg: R) -> Result<(), Self::Error> {
-        self.cs_mag.set_low().map_err(Error::Pin)?;
-
-        // note that multiple byte writing needs to set the MS bit
-        let payload: [u8; 2] = [R::ADDR, reg.data()];
-        let result = self.spi.write(&payload).map_err(Error::Comm);
-
-        self.cs_mag.set_high().map_err(Error::Pin)?;
-        result
+        Self::write_byte(&mut self.spi, &mut self.cs_mag, R::ADDR, reg.data())
     }
 }
 
 /// Read data
+///
+/// Double registers are always assembled as little-endian, i.e. the byte at the
+/// lower address is the least significant byte, independent of the host's
+/// native byte order.
 pub trait ReadData: private::Sealed {
     /// Error type
     type Error;
@@ -107,6 +131,37 @@ pub trait ReadData: private::Sealed {
     ) -> Result<R::Output, Self::Error>;
 }
 
+fn read_register<R: RegRead, E>(
+    read_bytes: impl FnOnce(u8, &mut [u8]) -> Result<(), E>,
+) -> Result<R::Output, E> {
+    let mut data = [0];
+    read_bytes(R::ADDR, &mut data)?;
+
+    Ok(R::from_data(data[0]))
+}
+
+fn read_double_register<R: RegRead<u16>, E>(
+    read_bytes: impl FnOnce(u8, &mut [u8]) -> Result<(), E>,
+) -> Result<R::Output, E> {
+    let mut data = [0; 2];
+    read_bytes(R::ADDR, &mut data)?;
+
+    Ok(R::from_data(u16::from_le_bytes(data)))
+}
+
+fn read_3_double_registers<R: RegRead<(u16, u16, u16)>, E>(
+    read_bytes: impl FnOnce(u8, &mut [u8]) -> Result<(), E>,
+) -> Result<R::Output, E> {
+    let mut data = [0; 6];
+    read_bytes(R::ADDR, &mut data)?;
+
+    Ok(R::from_data((
+        u16::from_le_bytes([data[0], data[1]]),
+        u16::from_le_bytes([data[2], data[3]]),
+        u16::from_le_bytes([data[4], data[5]]),
+    )))
+}
+
 impl<I2C, E> ReadData for I2cInterface<I2C>
 where
     I2C: i2c::WriteRead<Error = E>,
@@ -114,69 +169,69 @@ where
     type Error = Error<E, ()>;
 
     fn read_accel_register<R: RegRead>(&mut self) -> Result<R::Output, Self::Error> {
-        self.read_register::<R>(ACCEL_ADDR)
+        read_register::<R, _>(|address, data| self.read_bytes(ACCEL_ADDR, address, data))
     }
 
     fn read_mag_register<R: RegRead>(&mut self) -> Result<R::Output, Self::Error> {
-        self.read_register::<R>(MAG_ADDR)
+        read_register::<R, _>(|address, data| self.read_bytes(MAG_ADDR, address, data))
     }
 
     fn read_accel_double_register<R: RegRead<u16>>(&mut self) -> Result<R::Output, Self::Error> {
-        self.read_double_register::<R>(ACCEL_ADDR)
+        read_double_register::<R, _>(|address, data| self.read_bytes(ACCEL_ADDR, address, data))
     }
 
     fn read_accel_3_double_registers<R: RegRead<(u16, u16, u16)>>(
         &mut self,
     ) -> Result<R::Output, Self::Error> {
-        self.read_3_double_registers::<R>(ACCEL_ADDR)
+        read_3_double_registers::<R, _>(|address, data| self.read_bytes(ACCEL_ADDR, address, data))
     }
 
     fn read_mag_3_double_registers<R: RegRead<(u16, u16, u16)>>(
         &mut self,
     ) -> Result<R::Output, Self::Error> {
-        self.read_3_double_registers::<R>(MAG_ADDR)
+        read_3_double_registers::<R, _>(|address, data| self.read_bytes(MAG_ADDR, address, data))
     }
 }
 
-impl<I2C, E> I2cInterface<I2C>
-where
-    I2C: i2c::WriteRead<Error = E>,
-{
-    fn read_register<R: RegRead>(&mut self, address: u8) -> Result<R::Output, Error<E, ()>> {
-        let mut data = [0];
-        self.i2c
-            .write_read(address, &[R::ADDR], &mut data)
-            .map_err(Error::Comm)?;
-
-        Ok(R::from_data(data[0]))
-    }
-
-    fn read_double_register<R: RegRead<u16>>(
+impl<I2C> I2cInterface<I2C> {
+    fn write_byte<E, CommE, PinE>(
         &mut self,
+        i2c_address: u8,
         address: u8,
-    ) -> Result<R::Output, Error<E, ()>> {
-        let mut data = [0; 2];
+        value: u8,
+    ) -> Result<(), Error<CommE, PinE>>
+    where
+        I2C: i2c::Write<Error = E>,
+        E: Into<CommE>,
+    {
         self.i2c
-            .write_read(address, &[R::ADDR | 0x80], &mut data)
-            .map_err(Error::Comm)?;
-
-        Ok(R::from_data(u16::from_le_bytes(data)))
+            .write(i2c_address, &[address, value])
+            .map_err(|e| Error::Comm(e.into()))
     }
 
-    fn read_3_double_registers<R: RegRead<(u16, u16, u16)>>(
+    fn read_bytes<E, CommE, PinE>(
         &mut self,
+        i2c_address: u8,
         address: u8,
-    ) -> Result<R::Output, Error<E, ()>> {
-        let mut data = [0; 6];
+        data: &mut [u8],
+    ) -> Result<(), Error<CommE, PinE>>
+    where
+        I2C: i2c::WriteRead<Error = E>,
+        E: Into<CommE>,
+    {
+        if data.len() > MAX_READ_LEN {
+            return Err(Error::InvalidInputData);
+        }
+
+        // multiple byte reading needs to set the auto-increment bit
+        let address = if data.len() > 1 {
+            address | 0x80
+        } else {
+            address
+        };
         self.i2c
-            .write_read(address, &[R::ADDR | 0x80], &mut data)
-            .map_err(Error::Comm)?;
-
-        Ok(R::from_data((
-            u16::from_le_bytes([data[0], data[1]]),
-            u16::from_le_bytes([data[2], data[3]]),
-            u16::from_le_bytes([data[4], data[5]]),
-        )))
+            .write_read(i2c_address, &[address], data)
+            .map_err(|e| Error::Comm(e.into()))
     }
 }
 
@@ -189,78 +244,397 @@ where
     type Error = Error<CommE, PinE>;
 
     fn read_accel_register<R: RegRead>(&mut self) -> Result<R::Output, Self::Error> {
-        self.cs_xl.set_low().map_err(Error::Pin)?;
-        let result = self.read_register::<R>();
-        self.cs_xl.set_high().map_err(Error::Pin)?;
-        result
+        read_register::<R, _>(|address, data| {
+            Self::read_bytes(&mut self.spi, &mut self.cs_xl, address, data)
+        })
     }
 
     fn read_mag_register<R: RegRead>(&mut self) -> Result<R::Output, Self::Error> {
-        self.cs_mag.set_low().map_err(Error::Pin)?;
-        let result = self.read_register::<R>();
-        self.cs_mag.set_high().map_err(Error::Pin)?;
-        result
+        read_register::<R, _>(|address, data| {
+            Self::read_bytes(&mut self.spi, &mut self.cs_mag, address, data)
+        })
     }
 
     fn read_accel_double_register<R: RegRead<u16>>(&mut self) -> Result<R::Output, Self::Error> {
-        self.cs_xl.set_low().map_err(Error::Pin)?;
-        let result = self.read_double_register::<R>();
-        self.cs_xl.set_high().map_err(Error::Pin)?;
-        result
+        read_double_register::<R, _>(|address, data| {
+            Self::read_bytes(&mut self.spi, &mut self.cs_xl, address, data)
+        })
     }
 
     fn read_accel_3_double_registers<R: RegRead<(u16, u16, u16)>>(
         &mut self,
     ) -> Result<R::Output, Self::Error> {
-        self.cs_xl.set_low().map_err(Error::Pin)?;
-        let result = self.read_3_double_registers::<R>();
-        self.cs_xl.set_high().map_err(Error::Pin)?;
-        result
+        read_3_double_registers::<R, _>(|address, data| {
+            Self::read_bytes(&mut self.spi, &mut self.cs_xl, address, data)
+        })
     }
 
     fn read_mag_3_double_registers<R: RegRead<(u16, u16, u16)>>(
         &mut self,
     ) -> Result<R::Output, Self::Error> {
-        self.cs_mag.set_low().map_err(Error::Pin)?;
-        let result = self.read_3_double_registers::<R>();
-        self.cs_mag.set_high().map_err(Error::Pin)?;
+        read_3_double_registers::<R, _>(|address, data| {
+            Self::read_bytes(&mut self.spi, &mut self.cs_mag, address, data)
+        })
+    }
+}
+
+impl<SPI, CSXL, CSMAG> SpiInterface<SPI, CSXL, CSMAG> {
+    const SPI_RW: u8 = 1 << 7;
+    const SPI_MS: u8 = 1 << 6;
+
+    fn write_byte<CS, CE, PE, CommE, PinE>(
+        spi: &mut SPI,
+        cs: &mut CS,
+        address: u8,
+        value: u8,
+    ) -> Result<(), Error<CommE, PinE>>
+    where
+        SPI: spi::Write<u8, Error = CE>,
+        CS: OutputPin<Error = PE>,
+        CE: Into<CommE>,
+        PE: Into<PinE>,
+    {
+        cs.set_low().map_err(|e| Error::Pin(e.into()))?;
+
+        // note that multiple byte writing needs to set the MS bit
+        let result = spi
+            .write(&[address, value])
+            .map_err(|e| Error::Comm(e.into()));
+
+        cs.set_high().map_err(|e| Error::Pin(e.into()))?;
+        result
+    }
+
+    fn read_bytes<CS, CE, PE, CommE, PinE>(
+        spi: &mut SPI,
+        cs: &mut CS,
+        address: u8,
+        data: &mut [u8],
+    ) -> Result<(), Error<CommE, PinE>>
+    where
+        SPI: spi::Transfer<u8, Error = CE>,
+        CS: OutputPin<Error = PE>,
+        CE: Into<CommE>,
+        PE: Into<PinE>,
+    {
+        let len = data.len();
+        if len > MAX_READ_LEN {
+            return Err(Error::InvalidInputData);
+        }
+
+        let mut buffer = [0; MAX_READ_LEN + 1];
+        buffer[0] = if len > 1 {
+            Self::SPI_RW | Self::SPI_MS | address
+        } else {
+            Self::SPI_RW | address
+        };
+
+        cs.set_low().map_err(|e| Error::Pin(e.into()))?;
+        let result = spi
+            .transfer(&mut buffer[..=len])
+            .map(|buffer| data.copy_from_slice(&buffer[1..]))
+            .map_err(|e| Error::Comm(e.into()));
+        cs.set_high().map_err(|e| Error::Pin(e.into()))?;
         result
     }
 }
 
-impl<SPI, CSXL, CSMAG, CommE, PinE> SpiInterface<SPI, CSXL, CSMAG>
+/// Byte-level register access which can be used as a trait object.
+///
+/// Unlike [`ReadData`] and [`WriteData`], this trait has no generic methods,
+/// so it can be used through [`DynInterface`] to select the interface at runtime.
+///
+/// It is implemented for [`I2cInterface`] and [`SpiInterface`] for all error
+/// types their bus and pin errors can be converted into, so both can be used
+/// as the same trait object, e.g. with an error `enum` covering both buses.
+/// Since [`I2cInterface`] has no chip-select pins, it never returns [`Error::Pin`].
+///
+/// This trait is not sealed, so it can also be implemented for other
+/// interfaces, e.g. a bus shared with other devices.
+pub trait RegisterAccess<CommE, PinE> {
+    /// Write to an u8 accelerometer register
+    fn write_accel_byte(&mut self, address: u8, value: u8) -> Result<(), Error<CommE, PinE>>;
+    /// Write to an u8 magnetometer register
+    fn write_mag_byte(&mut self, address: u8, value: u8) -> Result<(), Error<CommE, PinE>>;
+    /// Read consecutive accelerometer registers.
+    ///
+    /// Returns [`Error::InvalidInputData`] if more than 6 registers are requested.
+    fn read_accel_bytes(&mut self, address: u8, data: &mut [u8]) -> Result<(), Error<CommE, PinE>>;
+    /// Read consecutive magnetometer registers.
+    ///
+    /// Returns [`Error::InvalidInputData`] if more than 6 registers are requested.
+    fn read_mag_bytes(&mut self, address: u8, data: &mut [u8]) -> Result<(), Error<CommE, PinE>>;
+}
+
+impl<I2C, E, CommE, PinE> RegisterAccess<CommE, PinE> for I2cInterface<I2C>
 where
-    SPI: spi::Transfer<u8, Error = CommE>,
-    CSXL: OutputPin<Error = PinE>,
-    CSMAG: OutputPin<Error = PinE>,
+    I2C: i2c::Write<Error = E> + i2c::WriteRead<Error = E>,
+    E: Into<CommE>,
 {
-    const SPI_RW: u8 = 1 << 7;
-    const SPI_MS: u8 = 1 << 6;
+    fn write_accel_byte(&mut self, address: u8, value: u8) -> Result<(), Error<CommE, PinE>> {
+        self.write_byte(ACCEL_ADDR, address, value)
+    }
 
-    fn read_register<R: RegRead>(&mut self) -> Result<R::Output, Error<CommE, PinE>> {
-        let mut data = [Self::SPI_RW | R::ADDR, 0];
-        self.spi.transfer(&mut data).map_err(Error::Comm)?;
+    fn write_mag_byte(&mut self, address: u8, value: u8) -> Result<(), Error<CommE, PinE>> {
+        self.write_byte(MAG_ADDR, address, value)
+    }
 
-        Ok(R::from_data(data[1]))
+    fn read_accel_bytes(&mut self, address: u8, data: &mut [u8]) -> Result<(), Error<CommE, PinE>> {
+        self.read_bytes(ACCEL_ADDR, address, data)
     }
 
-    fn read_double_register<R: RegRead<u16>>(&mut self) -> Result<R::Output, Error<CommE, PinE>> {
-        let mut data = [Self::SPI_RW | Self::SPI_MS | R::ADDR, 0, 0];
-        self.spi.transfer(&mut data).map_err(Error::Comm)?;
+    fn read_mag_bytes(&mut self, address: u8, data: &mut [u8]) -> Result<(), Error<CommE, PinE>> {
+        self.read_bytes(MAG_ADDR, address, data)
+    }
+}
 
-        Ok(R::from_data(u16::from_le_bytes([data[1], data[2]])))
+impl<SPI, CSXL, CSMAG, CE, PE, CommE, PinE> RegisterAccess<CommE, PinE>
+    for SpiInterface<SPI, CSXL, CSMAG>
+where
+    SPI: spi::Write<u8, Error = CE> + spi::Transfer<u8, Error = CE>,
+    CSXL: OutputPin<Error = PE>,
+    CSMAG: OutputPin<Error = PE>,
+    CE: Into<CommE>,
+    PE: Into<PinE>,
+{
+    fn write_accel_byte(&mut self, address: u8, value: u8) -> Result<(), Error<CommE, PinE>> {
+        Self::write_byte(&mut self.spi, &mut self.cs_xl, address, value)
     }
 
-    fn read_3_double_registers<R: RegRead<(u16, u16, u16)>>(
+    fn write_mag_byte(&mut self, address: u8, value: u8) -> Result<(), Error<CommE, PinE>> {
+        Self::write_byte(&mut self.spi, &mut self.cs_mag, address, value)
+    }
+
+    fn read_accel_bytes(&mut self, address: u8, data: &mut [u8]) -> Result<(), Error<CommE, PinE>> {
+        Self::read_bytes(&mut self.spi, &mut self.cs_xl, address, data)
+    }
+
+    fn read_mag_bytes(&mut self, address: u8, data: &mut [u8]) -> Result<(), Error<CommE, PinE>> {
+        Self::read_bytes(&mut self.spi, &mut self.cs_mag, address, data)
+    }
+}
+
+/// Interface using dynamic dispatch.
+///
+/// This allows selecting the interface at runtime, e.g. I2C or SPI depending on
+/// the board, at the cost of a virtual call for each register access. See
+/// [`RegisterAccess`] for using a common error type for both buses.
+///
+/// The interface is borrowed, so it can also be owned by a `Box` if an
+/// allocator is available:
+///
+/// ```no_run
+/// # use embedded_hal_mock::{i2c::Mock as I2cMock, pin::Mock as PinMock, spi::Mock as SpiMock};
+/// # type CommE = embedded_hal_mock::MockError;
+/// # type PinE = embedded_hal_mock::MockError;
+/// # let (use_spi, i2c, spi, cs_accel, cs_mag) = (
+/// #     false,
+/// #     I2cMock::new(&[]),
+/// #     SpiMock::new(&[]),
+/// #     PinMock::new(&[]),
+/// #     PinMock::new(&[]),
+/// # );
+/// use lsm303agr::{
+///     interface::{DynInterface, I2cInterface, RegisterAccess, SpiInterface},
+///     Lsm303agr,
+/// };
+///
+/// let mut iface: Box<dyn RegisterAccess<CommE, PinE>> = if use_spi {
+///     Box::new(SpiInterface::new(spi, cs_accel, cs_mag))
+/// } else {
+///     Box::new(I2cInterface::new(i2c))
+/// };
+/// let mut sensor = Lsm303agr::new_with_dyn(DynInterface::new(iface.as_mut()));
+/// sensor.init().unwrap();
+/// ```
+pub struct DynInterface<'a, CommE, PinE> {
+    iface: &'a mut dyn RegisterAccess<CommE, PinE>,
+}
+
+impl<'a, CommE, PinE> DynInterface<'a, CommE, PinE> {
+    /// Create a new interface using dynamic dispatch.
+    pub fn new(iface: &'a mut dyn RegisterAccess<CommE, PinE>) -> Self {
+        Self { iface }
+    }
+}
+
+impl<'a, CommE, PinE> core::fmt::Debug for DynInterface<'a, CommE, PinE> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DynInterface").finish()
+    }
+}
+
+impl<'a, CommE, PinE> WriteData for DynInterface<'a, CommE, PinE> {
+    type Error = Error<CommE, PinE>;
+
+    fn write_accel_register<R: RegWrite>(&mut self, reg: R) -> Result<(), Self::Error> {
+        self.iface.write_accel_byte(R::ADDR, reg.data())
+    }
+
+    fn write_mag_register<R: RegWrite>(&mut self, reg: R) -> Result<(), Self::Error> {
+        self.iface.write_mag_byte(R::ADDR, reg.data())
+    }
+}
+
+impl<'a, CommE, PinE> ReadData for DynInterface<'a, CommE, PinE> {
+    type Error = Error<CommE, PinE>;
+
+    fn read_accel_register<R: RegRead>(&mut self) -> Result<R::Output, Self::Error> {
+        read_register::<R, _>(|address, data| self.iface.read_accel_bytes(address, data))
+    }
+
+    fn read_mag_register<R: RegRead>(&mut self) -> Result<R::Output, Self::Error> {
+        read_register::<R, _>(|address, data| self.iface.read_mag_bytes(address, data))
+    }
+
+    fn read_accel_double_register<R: RegRead<u16>>(&mut self) -> Result<R::Output, Self::Error> {
+        read_double_register::<R, _>(|address, data| self.iface.read_accel_bytes(address, data))
+    }
+
+    fn read_accel_3_double_registers<R: RegRead<(u16, u16, u16)>>(
+        &mut self,
+    ) -> Result<R::Output, Self::Error> {
+        read_3_double_registers::<R, _>(|address, data| self.iface.read_accel_bytes(address, data))
+    }
+
+    fn read_mag_3_double_registers<R: RegRead<(u16, u16, u16)>>(
+        &mut self,
+    ) -> Result<R::Output, Self::Error> {
+        read_3_double_registers::<R, _>(|address, data| self.iface.read_mag_bytes(address, data))
+    }
+}
+
+/// In-memory register bank for testing code using this driver.
+///
+/// Register writes are stored and returned by subsequent reads, so the
+/// driver can be used without scripting each bus transaction. The
+/// identification and default configuration registers are preloaded with
+/// their power-on values.
+///
+/// This is only available with the `fake-interface` feature.
+#[cfg(feature = "fake-interface")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FakeInterface {
+    accel: [u8; 0x80],
+    mag: [u8; 0x80],
+}
+
+#[cfg(feature = "fake-interface")]
+impl Default for FakeInterface {
+    fn default() -> Self {
+        let mut accel = [0; 0x80];
+        accel[usize::from(WhoAmIA::ADDR)] = WhoAmIA::ID;
+        accel[usize::from(CtrlReg1A::ADDR)] = CtrlReg1A::default().bits();
+
+        let mut mag = [0; 0x80];
+        mag[usize::from(WhoAmIM::ADDR)] = WhoAmIM::ID;
+        mag[usize::from(CfgRegAM::ADDR)] = CfgRegAM::default().bits();
+
+        Self { accel, mag }
+    }
+}
+
+#[cfg(feature = "fake-interface")]
+impl FakeInterface {
+    /// Create a new register bank with power-on defaults.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the value of an accelerometer register.
+    pub fn accel_register(&self, address: u8) -> u8 {
+        self.accel[usize::from(address & 0x7F)]
+    }
+
+    /// Set the value of an accelerometer register.
+    pub fn set_accel_register(&mut self, address: u8, value: u8) {
+        self.accel[usize::from(address & 0x7F)] = value;
+    }
+
+    /// Get the value of a magnetometer register.
+    pub fn mag_register(&self, address: u8) -> u8 {
+        self.mag[usize::from(address & 0x7F)]
+    }
+
+    /// Set the value of a magnetometer register.
+    pub fn set_mag_register(&mut self, address: u8, value: u8) {
+        self.mag[usize::from(address & 0x7F)] = value;
+    }
+
+    /// Set the raw acceleration output registers.
+    pub fn set_acceleration_raw(&mut self, x: u16, y: u16, z: u16) {
+        Self::set_3_double_registers(&mut self.accel, Acceleration::ADDR, (x, y, z));
+    }
+
+    /// Set the raw magnetic field output registers.
+    pub fn set_magnetic_field_raw(&mut self, x: u16, y: u16, z: u16) {
+        Self::set_3_double_registers(&mut self.mag, MagneticField::ADDR, (x, y, z));
+    }
+
+    fn set_3_double_registers(bank: &mut [u8; 0x80], address: u8, (x, y, z): (u16, u16, u16)) {
+        let start = usize::from(address);
+        bank[start..start + 2].copy_from_slice(&x.to_le_bytes());
+        bank[start + 2..start + 4].copy_from_slice(&y.to_le_bytes());
+        bank[start + 4..start + 6].copy_from_slice(&z.to_le_bytes());
+    }
+
+    fn read_double_register(bank: &[u8; 0x80], address: u8) -> u16 {
+        let start = usize::from(address);
+        u16::from_le_bytes([bank[start], bank[start + 1]])
+    }
+}
+
+#[cfg(feature = "fake-interface")]
+impl WriteData for FakeInterface {
+    type Error = Error<core::convert::Infallible, ()>;
+
+    fn write_accel_register<R: RegWrite>(&mut self, reg: R) -> Result<(), Self::Error> {
+        self.set_accel_register(R::ADDR, reg.data());
+        Ok(())
+    }
+
+    fn write_mag_register<R: RegWrite>(&mut self, reg: R) -> Result<(), Self::Error> {
+        self.set_mag_register(R::ADDR, reg.data());
+        Ok(())
+    }
+}
+
+#[cfg(feature = "fake-interface")]
+impl ReadData for FakeInterface {
+    type Error = Error<core::convert::Infallible, ()>;
+
+    fn read_accel_register<R: RegRead>(&mut self) -> Result<R::Output, Self::Error> {
+        Ok(R::from_data(self.accel_register(R::ADDR)))
+    }
+
+    fn read_mag_register<R: RegRead>(&mut self) -> Result<R::Output, Self::Error> {
+        Ok(R::from_data(self.mag_register(R::ADDR)))
+    }
+
+    fn read_accel_double_register<R: RegRead<u16>>(&mut self) -> Result<R::Output, Self::Error> {
+        Ok(R::from_data(Self::read_double_register(
+            &self.accel,
+            R::ADDR,
+        )))
+    }
+
+    fn read_accel_3_double_registers<R: RegRead<(u16, u16, u16)>>(
         &mut self,
-    ) -> Result<R::Output, Error<CommE, PinE>> {
-        let mut data = [Self::SPI_RW | Self::SPI_MS | R::ADDR, 0, 0, 0, 0, 0, 0];
-        self.spi.transfer(&mut data).map_err(Error::Comm)?;
+    ) -> Result<R::Output, Self::Error> {
+        Ok(R::from_data((
+            Self::read_double_register(&self.accel, R::ADDR),
+            Self::read_double_register(&self.accel, R::ADDR + 2),
+            Self::read_double_register(&self.accel, R::ADDR + 4),
+        )))
+    }
 
+    fn read_mag_3_double_registers<R: RegRead<(u16, u16, u16)>>(
+        &mut self,
+    ) -> Result<R::Output, Self::Error> {
         Ok(R::from_data((
-            u16::from_le_bytes([data[1], data[2]]),
-            u16::from_le_bytes([data[3], data[4]]),
-            u16::from_le_bytes([data[5], data[6]]),
+            Self::read_double_register(&self.mag, R::ADDR),
+            Self::read_double_register(&self.mag, R::ADDR + 2),
+            Self::read_double_register(&self.mag, R::ADDR + 4),
         )))
     }
 }
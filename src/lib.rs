@@ -6,27 +6,90 @@
 //!
 //! This driver allows you to:
 //! - Connect through I2C or SPI. See: [`new_with_i2c()`](Lsm303agr::new_with_i2c) and [`new_with_spi()`](Lsm303agr::new_with_spi) .
+//! - Select the interface at runtime. See: [`new_with_dyn()`](Lsm303agr::new_with_dyn).
+//! - Get the required SPI configuration. See: [`SPI_MODE`] and [`MAX_SPI_FREQ_HZ`].
 //! - Initialize the device. See: [`init()`](Lsm303agr::init).
+//! - Apply a configuration preset. See: [`preset_high_performance()`](Lsm303agr::preset_high_performance) and [`preset_low_power()`](Lsm303agr::preset_low_power).
+//! - Get the accelerometer and magnetometer IDs. See: [`identity()`](Lsm303agr::identity).
+//! - Reset the device. See: [`reset()`](Lsm303agr::reset).
+//! - Check whether the accelerometer reboot has completed. See: [`acc_reboot_complete()`](Lsm303agr::acc_reboot_complete).
+//! - Read back the device configuration. See: [`sync_from_device()`](Lsm303agr::sync_from_device).
+//! - Save and restore the cached device configuration. See: [`into_parts()`](Lsm303agr::into_parts) and [`from_parts()`](Lsm303agr::from_parts).
+//! - Dump the cached register values for debugging. See: [`debug_registers()`](Lsm303agr::debug_registers).
+//! - Read all device registers without side effects for diagnostics. See: [`dump_device()`](Lsm303agr::dump_device).
+//! - Verify register writes by reading them back. See: [`set_write_verification()`](Lsm303agr::set_write_verification).
+//! - Enable 3-wire SPI mode. See: [`enable_spi_3_wire()`](Lsm303agr::enable_spi_3_wire).
+//! - Disable the I2C interface when using SPI. See: [`disable_i2c()`](Lsm303agr::disable_i2c).
+//! - Use an in-memory register bank for testing (`fake-interface` feature). See: `new_with_fake()`.
 //! - Accelerometer:
 //!     - Read measured acceleration. See: [`acceleration()`](Lsm303agr::acceleration).
+//!     - Read the latest acceleration without reading from the FIFO. See: [`acc_peek_latest()`](Lsm303agr::acc_peek_latest).
+//!     - Read measured acceleration, retrying torn reads. See: [`acceleration_checked()`](Lsm303agr::acceleration_checked).
+//!     - Read measured acceleration together with the status. See: [`acceleration_with_status()`](Lsm303agr::acceleration_with_status).
+//!     - Read measured acceleration as [`MilliG`]. See: [`acceleration_typed()`](Lsm303agr::acceleration_typed).
+//!     - Read measured acceleration in a unit selected by type. See: [`acceleration_in()`](Lsm303agr::acceleration_in).
+//!     - Read measured acceleration along a single axis. See: [`acceleration_axis()`](Lsm303agr::acceleration_axis).
+//!     - Compute tilt angles (`math` feature). See: [`Acceleration::pitch_deg()`] and [`Acceleration::roll_deg()`].
+//!     - Read raw acceleration. See: [`acceleration_raw()`](Lsm303agr::acceleration_raw).
+//!     - Read measured acceleration with a timestamp. See: [`acceleration_with_timestamp()`](Lsm303agr::acceleration_with_timestamp).
+//!     - Read multiple acceleration samples. See: [`acceleration_batch()`](Lsm303agr::acceleration_batch).
 //!     - Get accelerometer status. See: [`accel_status()`](Lsm303agr::accel_status).
+//!     - Check whether the accelerometer is ready. See: [`acc_is_ready()`](Lsm303agr::acc_is_ready).
 //!     - Set accelerometer output data rate. See: [`set_accel_odr()`](Lsm303agr::set_accel_odr).
+//!     - Set accelerometer output data rate without waiting. See: [`set_accel_odr_no_wait()`](Lsm303agr::set_accel_odr_no_wait).
+//!     - Set accelerometer mode without waiting. See: [`set_accel_mode_no_wait()`](Lsm303agr::set_accel_mode_no_wait).
 //!     - Set accelerometer mode. See: [`set_accel_mode()`](Lsm303agr::set_accel_mode).
 //!     - Set accelerometer scale. See: [`set_accel_scale()`](Lsm303agr::set_accel_scale).
+//!     - Configure accelerometer high-pass filter. See: [`set_accel_high_pass_filter()`](Lsm303agr::set_accel_high_pass_filter).
+//!     - Use the current acceleration as the zero reference. See: [`acc_autozero()`](Lsm303agr::acc_autozero).
 //!     - Get accelerometer ID. See: [`accelerometer_id()`](Lsm303agr::accelerometer_id).
 //!     - Get temperature sensor status. See: [`temperature_status()`](Lsm303agr::temperature_status).
 //!     - Read measured temperature. See: [`temperature()`](Lsm303agr::temperature).
+//!     - Get the temperature data source. See: [`acc_temperature_source()`](Lsm303agr::acc_temperature_source).
 //!     - Configure FIFO. See: [`acc_set_fifo_mode()`](Lsm303agr::acc_set_fifo_mode).
-//!     - Enable/disable interrupts. See: [`acc_enable_interrupt()`](Lsm303agr::acc_enable_interrupt).
+//!     - Set FIFO watermark threshold. See: [`set_fifo_watermark()`](Lsm303agr::set_fifo_watermark).
+//!     - Set FIFO trigger source. See: [`acc_set_fifo_trigger()`](Lsm303agr::acc_set_fifo_trigger).
+//!     - Capture samples around a trigger event. See: [`acc_configure_trigger_capture()`](Lsm303agr::acc_configure_trigger_capture).
+//!     - Iterate over FIFO samples. See: [`acc_fifo_iter()`](Lsm303agr::acc_fifo_iter).
+//!     - Drain FIFO in stream mode. See: [`acc_stream_drain()`](Lsm303agr::acc_stream_drain).
+//!     - Check whether the FIFO watermark is reached. See: [`acc_fifo_watermark_reached()`](Lsm303agr::acc_fifo_watermark_reached).
+//!     - Get the number of samples stored in the FIFO. See: [`acc_fifo_sample_count()`](Lsm303agr::acc_fifo_sample_count).
+//!     - Iterate over FIFO samples tagged with the latest magnetic field. See: [`fifo_samples()`](Lsm303agr::fifo_samples).
+//!     - Enable/disable interrupts on the INT1 or INT2 pin. See: [`acc_enable_interrupt()`](Lsm303agr::acc_enable_interrupt).
+//!     - Enable FIFO watermark and overrun interrupts together. See: [`acc_enable_fifo_interrupts()`](Lsm303agr::acc_enable_fifo_interrupts).
+//!     - Enable data-ready interrupt. See: [`acc_enable_data_ready_interrupt()`](Lsm303agr::acc_enable_data_ready_interrupt).
+//!     - Configure interrupt generator. See: [`acc_configure_interrupt_generator()`](Lsm303agr::acc_configure_interrupt_generator).
+//!     - Get interrupt generator source. See: [`acc_interrupt_source()`](Lsm303agr::acc_interrupt_source).
+//!     - Get the sources of both interrupt generators. See: [`acc_interrupt_sources()`](Lsm303agr::acc_interrupt_sources).
+//!     - Configure click detection. See: [`acc_configure_click()`](Lsm303agr::acc_configure_click).
+//!     - Configure wake on motion. See: [`acc_configure_wake_on_motion()`](Lsm303agr::acc_configure_wake_on_motion).
+//!     - Check the self-test response. See: [`accel_self_test_passed()`].
 //! - Magnetometer:
 //!     - Get the magnetometer status. See: [`mag_status()`](Lsm303agr::mag_status).
+//!     - Check whether new magnetometer data is available. See: [`mag_data_ready()`](Lsm303agr::mag_data_ready).
+//!     - Clear a magnetometer data overrun. See: [`mag_clear_overrun()`](Lsm303agr::mag_clear_overrun).
+//!     - Recover the magnetometer from saturation. See: [`mag_recover_from_saturation()`](Lsm303agr::mag_recover_from_saturation).
 //!     - Change into continuous/one-shot mode. See: [`into_mag_continuous()`](Lsm303agr::into_mag_continuous).
+//!     - Check whether the magnetometer is in continuous mode. See: [`is_mag_continuous()`](Lsm303agr::is_mag_continuous).
+//!     - Put the magnetometer into idle mode. See: [`mag_idle()`](Lsm303agr::mag_idle).
 //!     - Read measured magnetic field. See: [`magnetic_field()`](Lsm303agr::magnetic_field).
+//!     - Take a single blocking magnetic field measurement. See: [`magnetic_field_blocking()`](Lsm303agr::magnetic_field_blocking).
+//!     - Read measured magnetic field as [`NanoTesla`]. See: [`magnetic_field_typed()`](Lsm303agr::magnetic_field_typed).
+//!     - Read raw magnetic field. See: [`magnetic_field_raw()`](Lsm303agr::magnetic_field_raw).
 //!     - Set magnetometer output data rate. See: [`set_mag_odr()`](Lsm303agr::set_mag_odr).
 //!     - Get magnetometer ID. See: [`magnetometer_id()`](Lsm303agr::magnetometer_id).
 //!     - Enable/disable magnetometer built in offset cancellation. See: [`enable_mag_offset_cancellation()`](Lsm303agr::enable_mag_offset_cancellation).
 //!     - Enable/disable magnetometer low-pass filter. See: [`mag_enable_low_pass_filter()`](Lsm303agr::mag_enable_low_pass_filter).
+//!     - Set magnetometer output byte order. See: [`mag_set_big_endian()`](Lsm303agr::mag_set_big_endian).
+//!     - Set magnetometer set pulse frequency. See: [`mag_set_pulse_frequency()`](Lsm303agr::mag_set_pulse_frequency).
 //!     - Set magnetometer mode. See: [`set_mag_mode()`](Lsm303agr::set_mag_mode).
+//!     - Enable/disable magnetometer low-power mode. See: [`mag_set_low_power()`](Lsm303agr::mag_set_low_power).
+//!     - Configure magnetometer interrupt. See: [`mag_configure_interrupt()`](Lsm303agr::mag_configure_interrupt).
+//!     - Set magnetometer interrupt polarity and latching. See: [`mag_set_interrupt_polarity()`](Lsm303agr::mag_set_interrupt_polarity), [`mag_set_interrupt_latched()`](Lsm303agr::mag_set_interrupt_latched).
+//!     - Output magnetometer data-ready signal on the DRDY pin. See: [`mag_enable_drdy_pin()`](Lsm303agr::mag_enable_drdy_pin).
+//!     - Enable magnetometer threshold interrupt on the INT_MAG pin. See: [`mag_enable_threshold_interrupt()`](Lsm303agr::mag_enable_threshold_interrupt).
+//!     - Route magnetometer interrupt to the INT_MAG pin. See: [`mag_route_interrupt_to_pin()`](Lsm303agr::mag_route_interrupt_to_pin).
+//!     - Get magnetometer interrupt source. See: [`mag_interrupt_source()`](Lsm303agr::mag_interrupt_source).
 //!
 //! <!-- TODO
 //! [Introductory blog post](TODO)
@@ -122,20 +185,43 @@
 use core::marker::PhantomData;
 mod accel_mode_and_odr;
 mod device_impl;
+mod fifo;
+pub use crate::fifo::{FifoIter, Samples, StreamDrain};
 pub mod interface;
+
+/// SPI mode (CPOL = 1, CPHA = 1) required by the device.
+pub const SPI_MODE: embedded_hal::spi::Mode = embedded_hal::spi::MODE_3;
+
+/// Maximum SPI clock frequency supported by the device in Hz.
+pub const MAX_SPI_FREQ_HZ: u32 = 10_000_000;
+
 mod mag_mode_change;
 mod magnetometer;
+#[cfg(feature = "math")]
+mod math;
+mod presets;
 mod types;
 pub use crate::types::{
-    mode, AccelMode, AccelOutputDataRate, AccelScale, Acceleration, AccelerometerId, Error,
-    FifoMode, Interrupt, MagMode, MagOutputDataRate, MagneticField, MagnetometerId,
-    ModeChangeError, Status, Temperature, TemperatureStatus,
+    mode, AccelClickConfig, AccelHighPassCutoff, AccelHighPassMode, AccelInterruptConfig,
+    AccelInterruptSource, AccelMode, AccelOutputDataRate, AccelScale, AccelUnit, Acceleration,
+    AccelerometerId, Axis, Error, FifoMode, Interrupt, InterruptLine, InterruptSource,
+    InvalidValueError, MagInterruptConfig, MagInterruptSource, MagMode, MagOutputDataRate,
+    MagneticField, MagnetometerId, MilliG, ModeChangeError, NanoTesla, Status, TempSource,
+    Temperature, TemperatureStatus,
 };
+#[cfg(feature = "float")]
+pub use crate::types::{MeterPerSecSquared, G};
 mod register_address;
+mod self_test;
+pub use crate::self_test::{accel_self_test_limits_mg, accel_self_test_passed};
+mod snapshot;
+pub use crate::snapshot::DeviceSnapshot;
+mod with_delay;
 use crate::register_address::{
-    CfgRegAM, CfgRegBM, CfgRegCM, CtrlReg1A, CtrlReg3A, CtrlReg4A, CtrlReg5A, FifoCtrlRegA,
-    TempCfgRegA,
+    CfgRegAM, CfgRegBM, CfgRegCM, CtrlReg1A, CtrlReg2A, CtrlReg3A, CtrlReg4A, CtrlReg5A, CtrlReg6A,
+    FifoCtrlRegA, IntCrtlRegM, TempCfgRegA,
 };
+pub use crate::with_delay::WithDelay;
 
 /// LSM303AGR device driver
 #[derive(Debug)]
@@ -143,22 +229,96 @@ pub struct Lsm303agr<DI, MODE> {
     /// Digital interface: I2C or SPI
     iface: DI,
     ctrl_reg1_a: CtrlReg1A,
+    ctrl_reg2_a: CtrlReg2A,
     ctrl_reg3_a: CtrlReg3A,
     ctrl_reg4_a: CtrlReg4A,
     ctrl_reg5_a: CtrlReg5A,
+    ctrl_reg6_a: CtrlReg6A,
     cfg_reg_a_m: CfgRegAM,
     cfg_reg_b_m: CfgRegBM,
     cfg_reg_c_m: CfgRegCM,
+    int_crtl_reg_m: IntCrtlRegM,
     temp_cfg_reg_a: TempCfgRegA,
     fifo_ctrl_reg_a: FifoCtrlRegA,
     accel_odr: Option<AccelOutputDataRate>,
+    /// Whether a one-shot magnetometer measurement was triggered since entering one-shot mode.
+    mag_one_shot_triggered: bool,
+    verify_writes: bool,
     _mag_mode: PhantomData<MODE>,
 }
 
+/// Snapshot of the device configuration cached by the driver.
+///
+/// See [`Lsm303agr::into_parts`] and [`Lsm303agr::from_parts`].
+#[derive(Debug)]
+pub struct DeviceConfig<MODE> {
+    ctrl_reg1_a: CtrlReg1A,
+    ctrl_reg2_a: CtrlReg2A,
+    ctrl_reg3_a: CtrlReg3A,
+    ctrl_reg4_a: CtrlReg4A,
+    ctrl_reg5_a: CtrlReg5A,
+    ctrl_reg6_a: CtrlReg6A,
+    cfg_reg_a_m: CfgRegAM,
+    cfg_reg_b_m: CfgRegBM,
+    cfg_reg_c_m: CfgRegCM,
+    int_crtl_reg_m: IntCrtlRegM,
+    temp_cfg_reg_a: TempCfgRegA,
+    fifo_ctrl_reg_a: FifoCtrlRegA,
+    accel_odr: Option<AccelOutputDataRate>,
+    mag_one_shot_triggered: bool,
+    verify_writes: bool,
+    _mag_mode: PhantomData<MODE>,
+}
+
+impl<MODE> Clone for DeviceConfig<MODE> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<MODE> Copy for DeviceConfig<MODE> {}
+
+/// Values of the control and configuration registers cached by the driver.
+///
+/// See [`Lsm303agr::debug_registers`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegisterDump {
+    /// `CTRL_REG1_A`
+    pub ctrl_reg1_a: u8,
+    /// `CTRL_REG2_A`
+    pub ctrl_reg2_a: u8,
+    /// `CTRL_REG3_A`
+    pub ctrl_reg3_a: u8,
+    /// `CTRL_REG4_A`
+    pub ctrl_reg4_a: u8,
+    /// `CTRL_REG5_A`
+    pub ctrl_reg5_a: u8,
+    /// `CTRL_REG6_A`
+    pub ctrl_reg6_a: u8,
+    /// `TEMP_CFG_REG_A`
+    pub temp_cfg_reg_a: u8,
+    /// `FIFO_CTRL_REG_A`
+    pub fifo_ctrl_reg_a: u8,
+    /// `CFG_REG_A_M`
+    pub cfg_reg_a_m: u8,
+    /// `CFG_REG_B_M`
+    pub cfg_reg_b_m: u8,
+    /// `CFG_REG_C_M`
+    pub cfg_reg_c_m: u8,
+    /// `INT_CRTL_REG_M`
+    pub int_crtl_reg_m: u8,
+}
+
 mod private {
-    use crate::interface;
+    use crate::{interface, mode};
     pub trait Sealed {}
 
+    impl Sealed for mode::MagOneShot {}
+    impl Sealed for mode::MagContinuous {}
+
     impl<SPI, CSXL, CSMAG> Sealed for interface::SpiInterface<SPI, CSXL, CSMAG> {}
     impl<I2C> Sealed for interface::I2cInterface<I2C> {}
+    impl<'a, CommE, PinE> Sealed for interface::DynInterface<'a, CommE, PinE> {}
+    #[cfg(feature = "fake-interface")]
+    impl Sealed for interface::FakeInterface {}
 }
@@ -1,31 +1,58 @@
 use crate::{
     interface::{ReadData, WriteData},
-    mode, Error, Lsm303agr, ModeChangeError, PhantomData,
+    mode::{self, MagModeMarker},
+    Error, Lsm303agr, ModeChangeError, PhantomData,
 };
 
+impl<DI, MODE> Lsm303agr<DI, MODE>
+where
+    MODE: MagModeMarker,
+{
+    /// Check whether the magnetometer is in continuous mode.
+    ///
+    /// This is determined by the mode type, so no communication with the device is needed.
+    #[inline]
+    pub fn is_mag_continuous(&self) -> bool {
+        MODE::IS_CONTINUOUS
+    }
+}
+
 impl<DI, CommE, PinE> Lsm303agr<DI, mode::MagOneShot>
 where
     DI: ReadData<Error = Error<CommE, PinE>> + WriteData<Error = Error<CommE, PinE>>,
 {
     /// Change the magnetometer to continuous measurement mode
+    ///
+    /// The cached configuration is only updated if the mode change succeeds,
+    /// otherwise the original device is returned unchanged.
+    ///
+    /// If the mode change fails with `Error::WriteVerification`, `CFG_REG_A_M` was
+    /// already written, so the cached configuration of the returned device may not
+    /// match the device anymore. Use [`sync_from_device()`](Lsm303agr::sync_from_device)
+    /// to read back the actual configuration.
     pub fn into_mag_continuous(
         mut self,
     ) -> Result<Lsm303agr<DI, mode::MagContinuous>, ModeChangeError<CommE, PinE, Self>> {
         let cfg = self.cfg_reg_a_m.continuous_mode();
-        match self.iface.write_mag_register(cfg) {
+        match self.write_mag_register(cfg) {
             Err(error) => Err(ModeChangeError { error, dev: self }),
             Ok(_) => Ok(Lsm303agr {
                 iface: self.iface,
                 ctrl_reg1_a: self.ctrl_reg1_a,
+                ctrl_reg2_a: self.ctrl_reg2_a,
                 ctrl_reg3_a: self.ctrl_reg3_a,
                 ctrl_reg4_a: self.ctrl_reg4_a,
                 ctrl_reg5_a: self.ctrl_reg5_a,
+                ctrl_reg6_a: self.ctrl_reg6_a,
                 cfg_reg_a_m: cfg,
                 cfg_reg_b_m: self.cfg_reg_b_m,
                 cfg_reg_c_m: self.cfg_reg_c_m,
+                int_crtl_reg_m: self.int_crtl_reg_m,
                 temp_cfg_reg_a: self.temp_cfg_reg_a,
                 fifo_ctrl_reg_a: self.fifo_ctrl_reg_a,
-                accel_odr: None,
+                accel_odr: self.accel_odr,
+                mag_one_shot_triggered: self.mag_one_shot_triggered,
+                verify_writes: self.verify_writes,
                 _mag_mode: PhantomData,
             }),
         }
@@ -40,24 +67,37 @@ where
     ///
     /// After this the magnetometer is in idle mode until a one-shot measurement
     /// is started.
+    ///
+    /// The cached configuration is only updated if the mode change succeeds,
+    /// otherwise the original device is returned unchanged.
+    ///
+    /// If the mode change fails with `Error::WriteVerification`, `CFG_REG_A_M` was
+    /// already written, so the cached configuration of the returned device may not
+    /// match the device anymore. Use [`sync_from_device()`](Lsm303agr::sync_from_device)
+    /// to read back the actual configuration.
     pub fn into_mag_one_shot(
         mut self,
     ) -> Result<Lsm303agr<DI, mode::MagOneShot>, ModeChangeError<CommE, PinE, Self>> {
         let cfg = self.cfg_reg_a_m.idle_mode();
-        match self.iface.write_mag_register(cfg) {
+        match self.write_mag_register(cfg) {
             Err(error) => Err(ModeChangeError { error, dev: self }),
             Ok(_) => Ok(Lsm303agr {
                 iface: self.iface,
                 ctrl_reg1_a: self.ctrl_reg1_a,
+                ctrl_reg2_a: self.ctrl_reg2_a,
                 ctrl_reg3_a: self.ctrl_reg3_a,
                 ctrl_reg4_a: self.ctrl_reg4_a,
                 ctrl_reg5_a: self.ctrl_reg5_a,
+                ctrl_reg6_a: self.ctrl_reg6_a,
                 cfg_reg_a_m: cfg,
                 cfg_reg_b_m: self.cfg_reg_b_m,
                 cfg_reg_c_m: self.cfg_reg_c_m,
+                int_crtl_reg_m: self.int_crtl_reg_m,
                 temp_cfg_reg_a: self.temp_cfg_reg_a,
                 fifo_ctrl_reg_a: self.fifo_ctrl_reg_a,
-                accel_odr: None,
+                accel_odr: self.accel_odr,
+                mag_one_shot_triggered: false,
+                verify_writes: self.verify_writes,
                 _mag_mode: PhantomData,
             }),
         }
@@ -1,10 +1,11 @@
 use embedded_hal::blocking::delay::DelayUs;
 
 use crate::{
+    device_impl::BOOT_TIME_US,
     interface::{ReadData, WriteData},
     mode,
     register_address::{CfgRegAM, CfgRegBM},
-    Error, Lsm303agr, MagMode, MagOutputDataRate, MagneticField,
+    Error, Lsm303agr, MagMode, MagOutputDataRate, MagneticField, NanoTesla,
 };
 
 impl<DI, CommE, PinE, MODE> Lsm303agr<DI, MODE>
@@ -13,6 +14,10 @@ where
 {
     /// Set magnetometer output data rate.
     ///
+    /// Only the output data rate bits of `CFG_REG_A_M` are changed, the power and
+    /// operating mode bits are written unchanged. In continuous mode, the new output
+    /// data rate takes effect with the next measurement cycle.
+    ///
     #[doc = include_str!("delay.md")]
     pub fn set_mag_odr<D: DelayUs<u32>>(
         &mut self,
@@ -24,11 +29,11 @@ where
         let old_odr = rega.odr();
 
         let rega = rega.with_odr(odr);
-        self.iface.write_mag_register(rega)?;
+        self.write_mag_register(rega)?;
         self.cfg_reg_a_m = rega;
 
         if old_odr != odr && self.cfg_reg_b_m.offset_cancellation() {
-            // Mode did not change, so only wait for 1/ODR ms.
+            // Mode did not change, so only wait for 1/ODR.
             delay.delay_us(odr.turn_on_time_us_frac_1());
         }
 
@@ -48,7 +53,7 @@ where
         let old_mode = rega.mode();
 
         let rega = rega.with_mode(mode);
-        self.iface.write_mag_register(rega)?;
+        self.write_mag_register(rega)?;
         self.cfg_reg_a_m = rega;
 
         if old_mode != mode {
@@ -58,10 +63,99 @@ where
         Ok(())
     }
 
+    /// Enable or disable magnetometer low-power mode.
+    ///
+    /// This is equivalent to calling [`set_mag_mode()`](Lsm303agr::set_mag_mode) with
+    /// [`MagMode::LowPower`] or [`MagMode::HighResolution`], respectively.
+    ///
+    /// All magnetometer output data rates are supported in both modes, so the
+    /// output data rate is left unchanged.
+    ///
+    #[doc = include_str!("delay.md")]
+    pub fn mag_set_low_power<D: DelayUs<u32>>(
+        &mut self,
+        delay: &mut D,
+        enabled: bool,
+    ) -> Result<(), Error<CommE, PinE>> {
+        let mode = if enabled {
+            MagMode::LowPower
+        } else {
+            MagMode::HighResolution
+        };
+
+        self.set_mag_mode(delay, mode)
+    }
+
     /// Get magnetometer power/resolution mode.
     pub fn get_mag_mode(&self) -> MagMode {
         self.cfg_reg_a_m.mode()
     }
+
+    /// Get magnetometer output data rate.
+    pub fn get_mag_odr(&self) -> MagOutputDataRate {
+        self.cfg_reg_a_m.odr()
+    }
+
+    /// Get the raw magnetic field for the X-, Y- and Z-directions.
+    ///
+    /// This reads the output registers as they are, taking the configured byte order
+    /// into account. Unlike [`magnetic_field()`](Lsm303agr::magnetic_field), no
+    /// measurement is started in one-shot mode.
+    pub fn magnetic_field_raw(&mut self) -> Result<(i16, i16, i16), Error<CommE, PinE>> {
+        let field = self.read_mag_xyz()?;
+
+        Ok((field.x as i16, field.y as i16, field.z as i16))
+    }
+
+    /// Recover the magnetometer after exposure to a strong magnetic field.
+    ///
+    /// This reboots the magnetometer memory content and waits 5 ms for it to boot.
+    /// On boot, the magnetometer applies a set pulse which re-magnetizes the
+    /// sensing elements. The cached configuration, including the interrupt
+    /// configuration, is written back afterwards.
+    ///
+    /// In one-shot mode, the magnetometer is left in idle mode, so the next call to
+    /// [`magnetic_field()`](Lsm303agr::magnetic_field) starts a new measurement.
+    pub fn mag_recover_from_saturation<D: DelayUs<u32>>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(), Error<CommE, PinE>> {
+        // Do not start a new single measurement when writing back the configuration.
+        let rega = if self.cfg_reg_a_m.is_single_mode() {
+            self.cfg_reg_a_m.idle_mode()
+        } else {
+            self.cfg_reg_a_m
+        };
+
+        self.write_mag_register(rega | CfgRegAM::REBOOT)?;
+
+        delay.delay_us(BOOT_TIME_US);
+
+        self.write_mag_register(rega)?;
+        self.cfg_reg_a_m = rega;
+        self.mag_one_shot_triggered = false;
+        self.write_mag_register(self.cfg_reg_b_m)?;
+        self.write_mag_register(self.cfg_reg_c_m)?;
+        self.write_mag_register(self.int_crtl_reg_m)?;
+
+        Ok(())
+    }
+
+    /// Clear a magnetometer data overrun.
+    ///
+    /// When new data is generated before the previous data has been read, the
+    /// overrun flags in [`mag_status()`](Lsm303agr::mag_status) are set and the
+    /// output registers may not contain the most recent sample. This performs a
+    /// dummy read of the output registers, which clears the overrun condition
+    /// so that the next read returns fresh data.
+    ///
+    /// This is typically needed in continuous mode when the data is polled
+    /// slower than the output data rate.
+    pub fn mag_clear_overrun(&mut self) -> Result<(), Error<CommE, PinE>> {
+        self.iface
+            .read_mag_3_double_registers::<MagneticField>()
+            .map(drop)
+    }
 }
 
 impl<DI, CommE, PinE> Lsm303agr<DI, mode::MagContinuous>
@@ -70,7 +164,14 @@ where
 {
     /// Get the measured magnetic field.
     pub fn magnetic_field(&mut self) -> Result<MagneticField, Error<CommE, PinE>> {
-        self.iface.read_mag_3_double_registers::<MagneticField>()
+        self.read_mag_xyz()
+    }
+
+    /// Get the measured magnetic field in nT (nano-Tesla) for the X-, Y- and Z-directions.
+    pub fn magnetic_field_typed(
+        &mut self,
+    ) -> Result<(NanoTesla, NanoTesla, NanoTesla), Error<CommE, PinE>> {
+        self.magnetic_field().map(|field| field.xyz_nano_tesla())
     }
 
     /// Enable the magnetometer's built in offset cancellation.
@@ -81,7 +182,7 @@ where
     pub fn enable_mag_offset_cancellation(&mut self) -> Result<(), Error<CommE, PinE>> {
         let reg_b = self.cfg_reg_b_m | CfgRegBM::OFF_CANC;
 
-        self.iface.write_mag_register(reg_b)?;
+        self.write_mag_register(reg_b)?;
         self.cfg_reg_b_m = reg_b;
 
         Ok(())
@@ -91,7 +192,7 @@ where
     pub fn disable_mag_offset_cancellation(&mut self) -> Result<(), Error<CommE, PinE>> {
         let reg_b = self.cfg_reg_b_m & !CfgRegBM::OFF_CANC;
 
-        self.iface.write_mag_register(reg_b)?;
+        self.write_mag_register(reg_b)?;
         self.cfg_reg_b_m = reg_b;
 
         Ok(())
@@ -103,22 +204,85 @@ where
     DI: ReadData<Error = Error<CommE, PinE>> + WriteData<Error = Error<CommE, PinE>>,
 {
     /// Get the measured magnetic field.
+    ///
+    /// The first call after creating the device or changing into one-shot mode
+    /// always starts a new measurement, so data left over from before, e.g. from
+    /// continuous mode, is never returned.
     pub fn magnetic_field(&mut self) -> nb::Result<MagneticField, Error<CommE, PinE>> {
         let status = self.mag_status()?;
+        if !self.mag_one_shot_triggered {
+            if status.xyz_new_data() {
+                // Discard stale data so the new-data flag is cleared.
+                self.read_mag_xyz()?;
+            }
+
+            let cfg = self.cfg_reg_a_m.single_mode();
+            self.write_mag_register(cfg)?;
+            self.cfg_reg_a_m = cfg;
+            self.mag_one_shot_triggered = true;
+
+            return Err(nb::Error::WouldBlock);
+        }
+
         if status.xyz_new_data() {
-            Ok(self.iface.read_mag_3_double_registers::<MagneticField>()?)
+            Ok(self.read_mag_xyz()?)
         } else {
             let cfg = self.iface.read_mag_register::<CfgRegAM>()?;
             if !cfg.is_single_mode() {
                 // Switch to single mode.
                 let cfg = self.cfg_reg_a_m.single_mode();
-                self.iface.write_mag_register(cfg)?;
+                self.write_mag_register(cfg)?;
                 self.cfg_reg_a_m = cfg;
             }
             Err(nb::Error::WouldBlock)
         }
     }
 
+    /// Get the measured magnetic field in nT (nano-Tesla) for the X-, Y- and Z-directions.
+    pub fn magnetic_field_typed(
+        &mut self,
+    ) -> nb::Result<(NanoTesla, NanoTesla, NanoTesla), Error<CommE, PinE>> {
+        self.magnetic_field().map(|field| field.xyz_nano_tesla())
+    }
+
+    /// Take a single magnetic field measurement.
+    ///
+    /// This starts a one-shot measurement, waits for the turn-on time of the current
+    /// magnetometer mode using `delay` and then reads the result.
+    pub fn magnetic_field_blocking<D: DelayUs<u32>>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<MagneticField, Error<CommE, PinE>> {
+        let cfg = self.cfg_reg_a_m.single_mode();
+        self.write_mag_register(cfg)?;
+        self.cfg_reg_a_m = cfg;
+        self.mag_one_shot_triggered = true;
+
+        delay.delay_us(cfg.turn_on_time_us(self.cfg_reg_b_m.offset_cancellation()));
+
+        self.read_mag_xyz()
+    }
+
+    /// Put the magnetometer into idle mode.
+    ///
+    /// This stops an ongoing one-shot measurement to save power. The next call to
+    /// [`magnetic_field()`](Lsm303agr::magnetic_field) starts a new measurement,
+    /// discarding any unread data from before.
+    pub fn mag_idle(&mut self) -> Result<(), Error<CommE, PinE>> {
+        let cfg = self.cfg_reg_a_m.idle_mode();
+        self.write_mag_register(cfg)?;
+        self.cfg_reg_a_m = cfg;
+        self.mag_one_shot_triggered = false;
+
+        Ok(())
+    }
+
+    /// Check whether the magnetometer is in idle mode.
+    pub fn mag_is_idle(&mut self) -> Result<bool, Error<CommE, PinE>> {
+        let cfg = self.iface.read_mag_register::<CfgRegAM>()?;
+        Ok(cfg.is_idle_mode())
+    }
+
     /// Enable the magnetometer's built in offset cancellation.
     ///
     /// Offset cancellation has to be **managed by the user** in **single measurement** (OneShot) mode averaging
@@ -128,7 +292,7 @@ where
     pub fn enable_mag_offset_cancellation(&mut self) -> Result<(), Error<CommE, PinE>> {
         let reg_b = self.cfg_reg_b_m | CfgRegBM::OFF_CANC | CfgRegBM::OFF_CANC_ONE_SHOT;
 
-        self.iface.write_mag_register(reg_b)?;
+        self.write_mag_register(reg_b)?;
         self.cfg_reg_b_m = reg_b;
 
         Ok(())
@@ -138,7 +302,7 @@ where
     pub fn disable_mag_offset_cancellation(&mut self) -> Result<(), Error<CommE, PinE>> {
         let reg_b = self.cfg_reg_b_m & !(CfgRegBM::OFF_CANC | CfgRegBM::OFF_CANC_ONE_SHOT);
 
-        self.iface.write_mag_register(reg_b)?;
+        self.write_mag_register(reg_b)?;
         self.cfg_reg_b_m = reg_b;
 
         Ok(())
@@ -0,0 +1,61 @@
+//! Minimal floating-point functions, since `core` does not provide them.
+
+use core::f32::consts::{FRAC_PI_2, PI};
+
+#[inline]
+fn abs(x: f32) -> f32 {
+    if x < 0.0 {
+        -x
+    } else {
+        x
+    }
+}
+
+/// Square root using Newton's method.
+pub(crate) fn sqrt(x: f32) -> f32 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+
+    // Initial approximation by halving the exponent.
+    let mut y = f32::from_bits((x.to_bits() >> 1) + 0x1fbd_1df5);
+    for _ in 0..3 {
+        y = 0.5 * (y + x / y);
+    }
+    y
+}
+
+/// Arc tangent for `x` in [-1, 1], with a maximum error of about 1e-5 rad.
+fn atan_unit(x: f32) -> f32 {
+    let x2 = x * x;
+    x * (0.999_977_3
+        + x2 * (-0.332_623_5
+            + x2 * (0.193_543_5 + x2 * (-0.116_432_9 + x2 * (0.052_653_3 + x2 * -0.011_721_2)))))
+}
+
+/// Four-quadrant arc tangent of `y / x` in radians.
+pub(crate) fn atan2(y: f32, x: f32) -> f32 {
+    let (abs_y, abs_x) = (abs(y), abs(x));
+    if abs_x == 0.0 && abs_y == 0.0 {
+        return 0.0;
+    }
+
+    let angle = if abs_x >= abs_y {
+        atan_unit(abs_y / abs_x)
+    } else {
+        FRAC_PI_2 - atan_unit(abs_x / abs_y)
+    };
+    let angle = if x < 0.0 { PI - angle } else { angle };
+
+    if y < 0.0 {
+        -angle
+    } else {
+        angle
+    }
+}
+
+/// Convert radians to degrees.
+#[inline]
+pub(crate) fn to_degrees(rad: f32) -> f32 {
+    rad * (180.0 / PI)
+}
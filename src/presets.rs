@@ -0,0 +1,75 @@
+use embedded_hal::blocking::delay::DelayUs;
+
+use crate::{
+    interface::{ReadData, WriteData},
+    mode, AccelMode, AccelOutputDataRate, AccelScale, Error, Lsm303agr, MagMode, MagOutputDataRate,
+    ModeChangeError,
+};
+
+impl<DI, CommE, PinE> Lsm303agr<DI, mode::MagOneShot>
+where
+    DI: ReadData<Error = Error<CommE, PinE>> + WriteData<Error = Error<CommE, PinE>>,
+{
+    /// Configure the device for high performance.
+    ///
+    /// This sets:
+    /// - `CTRL_REG1_A`: accelerometer output data rate of 100 Hz, low-power mode disabled.
+    /// - `CTRL_REG4_A`: accelerometer high-resolution mode, ±2*g* scale.
+    /// - `CFG_REG_A_M`: magnetometer output data rate of 50 Hz, high-resolution and continuous mode.
+    ///
+    #[doc = include_str!("delay.md")]
+    pub fn preset_high_performance<D: DelayUs<u32>>(
+        mut self,
+        delay: &mut D,
+    ) -> Result<Lsm303agr<DI, mode::MagContinuous>, ModeChangeError<CommE, PinE, Self>> {
+        if let Err(error) = self.apply_preset(
+            delay,
+            AccelOutputDataRate::Hz100,
+            AccelMode::HighResolution,
+            MagOutputDataRate::Hz50,
+            MagMode::HighResolution,
+        ) {
+            return Err(ModeChangeError { error, dev: self });
+        }
+
+        self.into_mag_continuous()
+    }
+
+    /// Configure the device for low power consumption.
+    ///
+    /// This sets:
+    /// - `CTRL_REG1_A`: accelerometer output data rate of 10 Hz, low-power mode enabled.
+    /// - `CTRL_REG4_A`: accelerometer high-resolution mode disabled, ±2*g* scale.
+    /// - `CFG_REG_A_M`: magnetometer output data rate of 10 Hz, low-power mode.
+    ///
+    /// The magnetometer stays in one-shot mode, i.e. it is idle between measurements.
+    ///
+    #[doc = include_str!("delay.md")]
+    pub fn preset_low_power<D: DelayUs<u32>>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(), Error<CommE, PinE>> {
+        self.apply_preset(
+            delay,
+            AccelOutputDataRate::Hz10,
+            AccelMode::LowPower,
+            MagOutputDataRate::Hz10,
+            MagMode::LowPower,
+        )
+    }
+
+    fn apply_preset<D: DelayUs<u32>>(
+        &mut self,
+        delay: &mut D,
+        accel_odr: AccelOutputDataRate,
+        accel_mode: AccelMode,
+        mag_odr: MagOutputDataRate,
+        mag_mode: MagMode,
+    ) -> Result<(), Error<CommE, PinE>> {
+        self.set_accel_odr(delay, accel_odr)?;
+        self.set_accel_mode(delay, accel_mode)?;
+        self.set_accel_scale(AccelScale::G2)?;
+        self.set_mag_mode(delay, mag_mode)?;
+        self.set_mag_odr(delay, mag_odr)
+    }
+}
@@ -1,6 +1,7 @@
 use crate::types::{
-    AccelOutputDataRate, AccelScale, AccelerometerId, FifoMode, Interrupt, MagMode,
-    MagOutputDataRate, MagnetometerId, StatusFlags,
+    AccelClickConfig, AccelHighPassCutoff, AccelHighPassMode, AccelInterruptConfig,
+    AccelInterruptFlags, AccelOutputDataRate, AccelScale, AccelerometerId, FifoMode,
+    InterruptSource, MagInterruptConfig, MagMode, MagOutputDataRate, MagnetometerId, StatusFlags,
 };
 
 pub trait RegRead<D = u8> {
@@ -141,8 +142,29 @@ impl CtrlReg1A {
     }
 }
 
+impl CtrlReg1A {
+    pub const fn odr(&self) -> Option<AccelOutputDataRate> {
+        let lp_enabled = self.contains(Self::LPEN);
+
+        Some(match self.intersection(Self::ODR).bits() >> 4 {
+            0b0001 => AccelOutputDataRate::Hz1,
+            0b0010 => AccelOutputDataRate::Hz10,
+            0b0011 => AccelOutputDataRate::Hz25,
+            0b0100 => AccelOutputDataRate::Hz50,
+            0b0101 => AccelOutputDataRate::Hz100,
+            0b0110 => AccelOutputDataRate::Hz200,
+            0b0111 => AccelOutputDataRate::Hz400,
+            0b1000 if lp_enabled => AccelOutputDataRate::Khz1_620LowPower,
+            0b1001 if lp_enabled => AccelOutputDataRate::Khz5_376LowPower,
+            0b1001 => AccelOutputDataRate::Khz1_344,
+            _ => return None,
+        })
+    }
+}
+
 register! {
   /// CTRL_REG2_A
+  #[derive(Default)]
   pub struct CtrlReg2A: 0x21 {
     const HPM1    = 0b10000000;
     const HPM0    = 0b01000000;
@@ -152,9 +174,32 @@ register! {
     const HPCLICK = 0b00000100;
     const HPIS2   = 0b00000010;
     const HPIS1   = 0b00000001;
+
+    const HPM = Self::HPM1.bits | Self::HPM0.bits;
+    const HPCF = Self::HPCF2.bits | Self::HPCF1.bits;
   }
 }
 
+impl CtrlReg2A {
+    pub const fn with_high_pass_mode(self, mode: AccelHighPassMode) -> Self {
+        match mode {
+            AccelHighPassMode::NormalWithReset => self.difference(Self::HPM),
+            AccelHighPassMode::Reference => self.difference(Self::HPM1).union(Self::HPM0),
+            AccelHighPassMode::Normal => self.union(Self::HPM1).difference(Self::HPM0),
+            AccelHighPassMode::AutoResetOnInterrupt => self.union(Self::HPM),
+        }
+    }
+
+    pub const fn with_high_pass_cutoff(self, cutoff: AccelHighPassCutoff) -> Self {
+        match cutoff {
+            AccelHighPassCutoff::OdrDiv50 => self.difference(Self::HPCF),
+            AccelHighPassCutoff::OdrDiv100 => self.difference(Self::HPCF2).union(Self::HPCF1),
+            AccelHighPassCutoff::OdrDiv200 => self.union(Self::HPCF2).difference(Self::HPCF1),
+            AccelHighPassCutoff::OdrDiv400 => self.union(Self::HPCF),
+        }
+    }
+}
+
 register! {
   /// CTRL_REG3_A
   #[derive(Default)]
@@ -170,28 +215,18 @@ register! {
 }
 
 impl CtrlReg3A {
-    pub const fn with_interrupt(self, interrupt: Interrupt) -> Self {
-        match interrupt {
-            Interrupt::Click => self.union(Self::I1_CLICK),
-            Interrupt::Aoi1 => self.union(Self::I1_AOI1),
-            Interrupt::Aoi2 => self.union(Self::I1_AOI2),
-            Interrupt::DataReady1 => self.union(Self::I1_DRDY1),
-            Interrupt::DataReady2 => self.union(Self::I1_DRDY2),
-            Interrupt::FifoWatermark => self.union(Self::I1_WTM),
-            Interrupt::FifoOverrun => self.union(Self::I1_OVERRUN),
-        }
-    }
-
-    pub const fn without_interrupt(self, interrupt: Interrupt) -> Self {
-        match interrupt {
-            Interrupt::Click => self.difference(Self::I1_CLICK),
-            Interrupt::Aoi1 => self.difference(Self::I1_AOI1),
-            Interrupt::Aoi2 => self.difference(Self::I1_AOI2),
-            Interrupt::DataReady1 => self.difference(Self::I1_DRDY1),
-            Interrupt::DataReady2 => self.difference(Self::I1_DRDY2),
-            Interrupt::FifoWatermark => self.difference(Self::I1_WTM),
-            Interrupt::FifoOverrun => self.difference(Self::I1_OVERRUN),
-        }
+    /// Get the flag routing the given interrupt source to the INT1 pin, if supported.
+    pub const fn interrupt_flag(source: InterruptSource) -> Option<Self> {
+        Some(match source {
+            InterruptSource::Click => Self::I1_CLICK,
+            InterruptSource::Aoi1 => Self::I1_AOI1,
+            InterruptSource::Aoi2 => Self::I1_AOI2,
+            InterruptSource::DataReady1 => Self::I1_DRDY1,
+            InterruptSource::DataReady2 => Self::I1_DRDY2,
+            InterruptSource::FifoWatermark => Self::I1_WTM,
+            InterruptSource::FifoOverrun => Self::I1_OVERRUN,
+            InterruptSource::Activity | InterruptSource::Boot => return None,
+        })
     }
 }
 
@@ -235,6 +270,10 @@ impl CtrlReg4A {
 
 register! {
   /// CTRL_REG5_A
+  ///
+  /// Bits 5 and 4 are reserved and must be 0. Since only the defined bits can be
+  /// set, and undefined bits are dropped when reading the register, they are never
+  /// written.
   #[derive(Default)]
   pub struct CtrlReg5A: 0x24 {
     const BOOT     = 0b10000000;
@@ -248,6 +287,7 @@ register! {
 
 register! {
   /// CTRL_REG6_A
+  #[derive(Default)]
   pub struct CtrlReg6A: 0x25 {
     const I2_CLICK_EN = 0b10000000;
     const I2_INT1     = 0b01000000;
@@ -258,6 +298,31 @@ register! {
   }
 }
 
+impl CtrlReg6A {
+    /// Get the flag routing the given interrupt source to the INT2 pin, if supported.
+    pub const fn interrupt_flag(source: InterruptSource) -> Option<Self> {
+        Some(match source {
+            InterruptSource::Click => Self::I2_CLICK_EN,
+            InterruptSource::Aoi1 => Self::I2_INT1,
+            InterruptSource::Aoi2 => Self::I2_INT2,
+            InterruptSource::Boot => Self::BOOT_I2,
+            InterruptSource::Activity => Self::P2_ACT,
+            InterruptSource::DataReady1
+            | InterruptSource::DataReady2
+            | InterruptSource::FifoWatermark
+            | InterruptSource::FifoOverrun => return None,
+        })
+    }
+}
+
+register! {
+  /// REFERENCE/DATACAPTURE_A
+  #[derive(Default)]
+  pub struct ReferenceA: 0x26 {
+    const REF = 0b11111111;
+  }
+}
+
 register! {
   /// STATUS_REG_A
   pub type StatusRegA: 0x27 = StatusFlags;
@@ -313,6 +378,8 @@ register! {
     const FSS2      = 0b00000100;
     const FSS1      = 0b00000010;
     const FSS0      = 0b00000001;
+
+    const FSS = Self::FSS4.bits | Self::FSS3.bits | Self::FSS2.bits | Self::FSS1.bits | Self::FSS0.bits;
   }
 }
 
@@ -336,16 +403,172 @@ register! {
   }
 }
 
+impl Int1CfgA {
+    pub const fn with_config(config: AccelInterruptConfig) -> Self {
+        let mut reg = Self::empty();
+        if config.and_combination {
+            reg = reg.union(Self::AOI);
+        }
+        if config.six_direction {
+            reg = reg.union(Self::D6);
+        }
+        if config.z_high {
+            reg = reg.union(Self::ZHIE);
+        }
+        if config.z_low {
+            reg = reg.union(Self::ZLIE);
+        }
+        if config.y_high {
+            reg = reg.union(Self::YHIE);
+        }
+        if config.y_low {
+            reg = reg.union(Self::YLIE);
+        }
+        if config.x_high {
+            reg = reg.union(Self::XHIE);
+        }
+        if config.x_low {
+            reg = reg.union(Self::XLIE);
+        }
+        reg
+    }
+}
+
 register! {
   /// INT1_SRC_A
-  pub struct Int1SrcA: 0x31 {
-    const IA = 0b01000000;
-    const ZH = 0b00100000;
-    const ZL = 0b00010000;
-    const YH = 0b00001000;
-    const YL = 0b00000100;
-    const XH = 0b00000010;
-    const XL = 0b00000001;
+  pub type Int1SrcA: 0x31 = AccelInterruptFlags;
+}
+
+register! {
+  /// INT2_SRC_A
+  pub type Int2SrcA: 0x35 = AccelInterruptFlags;
+}
+
+register! {
+  /// INT1_THS_A
+  #[derive(Default)]
+  pub struct Int1ThsA: 0x32 {
+    const THS = 0b01111111;
+  }
+}
+
+register! {
+  /// INT1_DURATION_A
+  #[derive(Default)]
+  pub struct Int1DurationA: 0x33 {
+    const D = 0b01111111;
+  }
+}
+
+register! {
+  /// INT2_CFG_A
+  pub struct Int2CfgA: 0x34 {
+    const AOI       = 0b10000000;
+    const D6        = 0b01000000;
+    const ZHIE      = 0b00100000;
+    const ZLIE      = 0b00010000;
+    const YHIE      = 0b00001000;
+    const YLIE      = 0b00000100;
+    const XHIE      = 0b00000010;
+    const XLIE      = 0b00000001;
+  }
+}
+
+register! {
+  /// INT2_THS_A
+  #[derive(Default)]
+  pub struct Int2ThsA: 0x36 {
+    const THS = 0b01111111;
+  }
+}
+
+register! {
+  /// INT2_DURATION_A
+  #[derive(Default)]
+  pub struct Int2DurationA: 0x37 {
+    const D = 0b01111111;
+  }
+}
+
+register! {
+  /// CLICK_CFG_A
+  pub struct ClickCfgA: 0x38 {
+    const ZD = 0b00100000;
+    const ZS = 0b00010000;
+    const YD = 0b00001000;
+    const YS = 0b00000100;
+    const XD = 0b00000010;
+    const XS = 0b00000001;
+  }
+}
+
+impl ClickCfgA {
+    pub const fn with_config(config: AccelClickConfig) -> Self {
+        let mut reg = Self::empty();
+        if config.z_double {
+            reg = reg.union(Self::ZD);
+        }
+        if config.z_single {
+            reg = reg.union(Self::ZS);
+        }
+        if config.y_double {
+            reg = reg.union(Self::YD);
+        }
+        if config.y_single {
+            reg = reg.union(Self::YS);
+        }
+        if config.x_double {
+            reg = reg.union(Self::XD);
+        }
+        if config.x_single {
+            reg = reg.union(Self::XS);
+        }
+        reg
+    }
+}
+
+register! {
+  /// CLICK_THS_A
+  pub struct ClickThsA: 0x3A {
+    const LIR_CLICK = 0b10000000;
+    const THS       = 0b01111111;
+  }
+}
+
+register! {
+  /// TIME_LIMIT_A
+  pub struct TimeLimitA: 0x3B {
+    const TLI = 0b01111111;
+  }
+}
+
+register! {
+  /// TIME_LATENCY_A
+  pub struct TimeLatencyA: 0x3C {
+    const TLA = 0b11111111;
+  }
+}
+
+register! {
+  /// TIME_WINDOW_A
+  pub struct TimeWindowA: 0x3D {
+    const TW = 0b11111111;
+  }
+}
+
+register! {
+  /// ACT_THS_A
+  #[derive(Default)]
+  pub struct ActThsA: 0x3E {
+    const ACTH = 0b01111111;
+  }
+}
+
+register! {
+  /// ACT_DUR_A
+  #[derive(Default)]
+  pub struct ActDurA: 0x3F {
+    const ACTD = 0b11111111;
   }
 }
 
@@ -409,7 +632,6 @@ impl CfgRegAM {
         self.difference(CfgRegAM::MD1).union(CfgRegAM::MD0) // 0b01
     }
 
-    #[cfg(test)]
     pub const fn is_idle_mode(&self) -> bool {
         self.contains(CfgRegAM::MD1) // 0b10 or 0b11
     }
@@ -478,11 +700,117 @@ register! {
   }
 }
 
+register! {
+  /// INT_CRTL_REG_M
+  #[derive(Default)]
+  pub struct IntCrtlRegM: 0x63 {
+    const XIEN = 0b10000000;
+    const YIEN = 0b01000000;
+    const ZIEN = 0b00100000;
+    const IEA  = 0b00000100;
+    const IEL  = 0b00000010;
+    const IEN  = 0b00000001;
+  }
+}
+
+impl IntCrtlRegM {
+    pub const fn with_config(config: MagInterruptConfig) -> Self {
+        let mut reg = Self::empty();
+        if config.x_enabled {
+            reg = reg.union(Self::XIEN);
+        }
+        if config.y_enabled {
+            reg = reg.union(Self::YIEN);
+        }
+        if config.z_enabled {
+            reg = reg.union(Self::ZIEN);
+        }
+        if config.active_high {
+            reg = reg.union(Self::IEA);
+        }
+        if config.latched {
+            reg = reg.union(Self::IEL);
+        }
+        if config.enabled {
+            reg = reg.union(Self::IEN);
+        }
+        reg
+    }
+}
+
+register! {
+  /// INT_SOURCE_REG_M
+  #[derive(Default)]
+  pub struct IntSourceRegM: 0x64 {
+    const PTH_X = 0b10000000;
+    const PTH_Y = 0b01000000;
+    const PTH_Z = 0b00100000;
+    const NTH_X = 0b00010000;
+    const NTH_Y = 0b00001000;
+    const NTH_Z = 0b00000100;
+    const MROI  = 0b00000010;
+    const INT   = 0b00000001;
+  }
+}
+
 register! {
   /// STATUS_REG_M
   pub type StatusRegM: 0x67 = StatusFlags;
 }
 
+macro_rules! output_register {
+  ($(#[doc = $doc:literal] $ty:ident: $addr:literal,)*) => {
+    $(
+      #[doc = $doc]
+      pub(crate) struct $ty;
+
+      impl RegRead<u16> for $ty {
+        type Output = u16;
+
+        const ADDR: u8 = $addr;
+
+        fn from_data(data: u16) -> Self::Output {
+          data
+        }
+      }
+    )*
+  };
+}
+
+output_register! {
+  /// OUT_X_L_A
+  OutXA: 0x28,
+  /// OUT_Y_L_A
+  OutYA: 0x2A,
+  /// OUT_Z_L_A
+  OutZA: 0x2C,
+}
+
+/// Get the bits of an accelerometer register which are not verified after writing.
+pub(crate) fn accel_unverified_bits(addr: u8) -> u8 {
+    if addr == CtrlReg5A::ADDR {
+        // Cleared after boot is complete.
+        CtrlReg5A::BOOT.bits()
+    } else {
+        0
+    }
+}
+
+/// Get the bits of a magnetometer register which are not verified after writing.
+pub(crate) fn mag_unverified_bits(addr: u8, data: u8) -> u8 {
+    if addr == CfgRegAM::ADDR {
+        let cfg = CfgRegAM::from_bits_truncate(data);
+        let mut bits = CfgRegAM::REBOOT | CfgRegAM::SOFT_RST;
+        if cfg.is_single_mode() {
+            // Returns to idle mode after the measurement.
+            bits |= CfgRegAM::MD;
+        }
+        bits.bits()
+    } else {
+        0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -509,30 +837,102 @@ mod tests {
         check_odr(AccelOutputDataRate::Hz100, 0b0101);
         check_odr(AccelOutputDataRate::Hz200, 0b0110);
         check_odr(AccelOutputDataRate::Hz400, 0b0111);
+
+        for odr in [
+            AccelOutputDataRate::Hz1,
+            AccelOutputDataRate::Hz10,
+            AccelOutputDataRate::Hz25,
+            AccelOutputDataRate::Hz50,
+            AccelOutputDataRate::Hz100,
+            AccelOutputDataRate::Hz200,
+            AccelOutputDataRate::Hz400,
+            AccelOutputDataRate::Khz1_344,
+            AccelOutputDataRate::Khz1_620LowPower,
+            AccelOutputDataRate::Khz5_376LowPower,
+        ] {
+            assert_eq!(ctrl.with_odr(odr).odr(), Some(odr));
+        }
+        assert_eq!(ctrl.odr(), None);
+    }
+
+    #[test]
+    fn ctrl_reg_2_a() {
+        let ctrl = CtrlReg2A::default();
+        assert_eq!(ctrl.bits(), 0);
+
+        let check_mode = |mode, value| {
+            assert_eq!(
+                ctrl.with_high_pass_mode(mode)
+                    .intersection(CtrlReg2A::HPM)
+                    .bits()
+                    >> 6,
+                value
+            );
+        };
+
+        check_mode(AccelHighPassMode::NormalWithReset, 0b00);
+        check_mode(AccelHighPassMode::Reference, 0b01);
+        check_mode(AccelHighPassMode::Normal, 0b10);
+        check_mode(AccelHighPassMode::AutoResetOnInterrupt, 0b11);
+
+        let check_cutoff = |cutoff, value| {
+            assert_eq!(
+                ctrl.with_high_pass_cutoff(cutoff)
+                    .intersection(CtrlReg2A::HPCF)
+                    .bits()
+                    >> 4,
+                value
+            );
+        };
+
+        check_cutoff(AccelHighPassCutoff::OdrDiv50, 0b00);
+        check_cutoff(AccelHighPassCutoff::OdrDiv100, 0b01);
+        check_cutoff(AccelHighPassCutoff::OdrDiv200, 0b10);
+        check_cutoff(AccelHighPassCutoff::OdrDiv400, 0b11);
     }
 
     #[test]
     fn ctrl_reg_3_a() {
-        let ctrl = CtrlReg3A::default();
-        let ctrl_all = CtrlReg3A::from_bits_truncate(0b11111110);
+        let mut bits = 0b10000000;
+        for source in [
+            InterruptSource::Click,
+            InterruptSource::Aoi1,
+            InterruptSource::Aoi2,
+            InterruptSource::DataReady1,
+            InterruptSource::DataReady2,
+            InterruptSource::FifoWatermark,
+            InterruptSource::FifoOverrun,
+        ] {
+            assert_eq!(CtrlReg3A::interrupt_flag(source).unwrap().bits(), bits);
+            bits >>= 1;
+        }
 
+        assert_eq!(CtrlReg3A::interrupt_flag(InterruptSource::Activity), None);
+        assert_eq!(CtrlReg3A::interrupt_flag(InterruptSource::Boot), None);
+    }
+
+    #[test]
+    fn ctrl_reg_6_a() {
         let mut bits = 0b10000000;
-        for interrupt in [
-            Interrupt::Click,
-            Interrupt::Aoi1,
-            Interrupt::Aoi2,
-            Interrupt::DataReady1,
-            Interrupt::DataReady2,
-            Interrupt::FifoWatermark,
-            Interrupt::FifoOverrun,
+        for source in [
+            InterruptSource::Click,
+            InterruptSource::Aoi1,
+            InterruptSource::Aoi2,
+            InterruptSource::Boot,
+            InterruptSource::Activity,
         ] {
-            assert_eq!(ctrl.with_interrupt(interrupt).bits(), bits,);
-            assert_eq!(
-                ctrl_all.without_interrupt(interrupt).bits(),
-                (!bits) & 0b11111110
-            );
+            assert_eq!(CtrlReg6A::interrupt_flag(source).unwrap().bits(), bits);
             bits >>= 1;
         }
+
+        for source in [
+            InterruptSource::DataReady1,
+            InterruptSource::DataReady2,
+            InterruptSource::FifoWatermark,
+            InterruptSource::FifoOverrun,
+        ] {
+            assert_eq!(CtrlReg6A::interrupt_flag(source), None);
+        }
     }
 
     #[test]
@@ -552,6 +952,13 @@ mod tests {
         assert_eq!(ctrl_g2.scale(), AccelScale::G2);
     }
 
+    #[test]
+    fn ctrl_reg_5_a() {
+        let ctrl = CtrlReg5A::from_data(0xFF);
+        assert_eq!(ctrl.data() & 0b00110000, 0, "Reserved bits are masked.");
+        assert_eq!(ctrl.data(), 0b11001111);
+    }
+
     #[test]
     fn cfg_reg_a_m() {
         let cfg = CfgRegAM::default();
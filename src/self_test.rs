@@ -0,0 +1,42 @@
+use crate::{AccelMode, AccelScale, Acceleration};
+
+/// Minimum accelerometer self-test output change in normal mode in LSb.
+const SELF_TEST_MIN_LSB: i32 = 17;
+
+/// Maximum accelerometer self-test output change in normal mode in LSb.
+const SELF_TEST_MAX_LSB: i32 = 360;
+
+/// Get the accelerometer self-test output change limits in m*g* for the given scale.
+///
+/// Returns the minimum and maximum absolute output change on each axis. The
+/// datasheet specifies 17 to 360 LSb in normal mode, which is converted using
+/// the normal mode sensitivity of `scale`, e.g. 68 to 1440 m*g* for ±2*g*.
+///
+/// Since the limits are returned in m*g*, they apply to measurements in any mode.
+pub const fn accel_self_test_limits_mg(scale: AccelScale) -> (i32, i32) {
+    let sensitivity = AccelMode::Normal.scaling_factor(scale);
+    (
+        SELF_TEST_MIN_LSB * sensitivity,
+        SELF_TEST_MAX_LSB * sensitivity,
+    )
+}
+
+/// Check whether the accelerometer self-test response is within the datasheet limits.
+///
+/// `before` is a measurement taken with self-test disabled and `after` one taken
+/// with self-test enabled. The test passes if the absolute output change on every
+/// axis is within the [limits](accel_self_test_limits_mg) for the scale of the
+/// measurements. Measurements taken with different scales never pass.
+pub fn accel_self_test_passed(before: &Acceleration, after: &Acceleration) -> bool {
+    if before.scale != after.scale {
+        return false;
+    }
+
+    let (min, max) = accel_self_test_limits_mg(after.scale);
+    let (bx, by, bz) = before.xyz_mg();
+    let (ax, ay, az) = after.xyz_mg();
+
+    [ax - bx, ay - by, az - bz]
+        .iter()
+        .all(|change| (min..=max).contains(&change.abs()))
+}
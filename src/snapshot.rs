@@ -0,0 +1,226 @@
+use core::fmt;
+
+use crate::{interface::ReadData, register_address::RegRead, Error, Lsm303agr};
+
+/// Register read as a plain byte, with its address known at compile time.
+struct RawRegister<const ADDR: u8>;
+
+impl<const ADDR: u8> RegRead for RawRegister<ADDR> {
+    type Output = u8;
+
+    const ADDR: u8 = ADDR;
+
+    fn from_data(data: u8) -> Self::Output {
+        data
+    }
+}
+
+/// Register can be read without side effects.
+const READ: bool = true;
+/// Reading the register has side effects, so it is skipped.
+const SKIP: bool = false;
+
+macro_rules! registers {
+    ($registers:ident, $read:ident, $read_register:ident, [$(($name:literal, $addr:literal, $flag:ident)),* $(,)?]) => {
+        const $registers: &[(&str, u8, bool)] = &[$(($name, $addr, $flag)),*];
+
+        fn $read<DI, CommE, PinE>(iface: &mut DI, address: u8) -> Result<u8, Error<CommE, PinE>>
+        where
+            DI: ReadData<Error = Error<CommE, PinE>>,
+        {
+            match address {
+                $($addr => iface.$read_register::<RawRegister<$addr>>(),)*
+                _ => Err(Error::InvalidInputData),
+            }
+        }
+    };
+}
+
+registers!(
+    ACCEL_REGISTERS,
+    read_accel,
+    read_accel_register,
+    [
+        ("STATUS_REG_AUX_A", 0x07, READ),
+        ("OUT_TEMP_L_A", 0x0C, READ),
+        ("OUT_TEMP_H_A", 0x0D, READ),
+        ("INT_COUNTER_REG_A", 0x0E, READ),
+        ("WHO_AM_I_A", 0x0F, READ),
+        ("TEMP_CFG_REG_A", 0x1F, READ),
+        ("CTRL_REG1_A", 0x20, READ),
+        ("CTRL_REG2_A", 0x21, READ),
+        ("CTRL_REG3_A", 0x22, READ),
+        ("CTRL_REG4_A", 0x23, READ),
+        ("CTRL_REG5_A", 0x24, READ),
+        ("CTRL_REG6_A", 0x25, READ),
+        // Resets the high-pass filter.
+        ("REFERENCE_A", 0x26, SKIP),
+        ("STATUS_REG_A", 0x27, READ),
+        // Removes a sample from the FIFO.
+        ("OUT_X_L_A", 0x28, SKIP),
+        ("OUT_X_H_A", 0x29, SKIP),
+        ("OUT_Y_L_A", 0x2A, SKIP),
+        ("OUT_Y_H_A", 0x2B, SKIP),
+        ("OUT_Z_L_A", 0x2C, SKIP),
+        ("OUT_Z_H_A", 0x2D, SKIP),
+        ("FIFO_CTRL_REG_A", 0x2E, READ),
+        ("FIFO_SRC_REG_A", 0x2F, READ),
+        ("INT1_CFG_A", 0x30, READ),
+        // Clears a latched interrupt.
+        ("INT1_SRC_A", 0x31, SKIP),
+        ("INT1_THS_A", 0x32, READ),
+        ("INT1_DURATION_A", 0x33, READ),
+        ("INT2_CFG_A", 0x34, READ),
+        // Clears a latched interrupt.
+        ("INT2_SRC_A", 0x35, SKIP),
+        ("INT2_THS_A", 0x36, READ),
+        ("INT2_DURATION_A", 0x37, READ),
+        ("CLICK_CFG_A", 0x38, READ),
+        // Clears a latched interrupt.
+        ("CLICK_SRC_A", 0x39, SKIP),
+        ("CLICK_THS_A", 0x3A, READ),
+        ("TIME_LIMIT_A", 0x3B, READ),
+        ("TIME_LATENCY_A", 0x3C, READ),
+        ("TIME_WINDOW_A", 0x3D, READ),
+        ("ACT_THS_A", 0x3E, READ),
+        ("ACT_DUR_A", 0x3F, READ),
+    ]
+);
+
+registers!(
+    MAG_REGISTERS,
+    read_mag,
+    read_mag_register,
+    [
+        ("OFFSET_X_REG_L_M", 0x45, READ),
+        ("OFFSET_X_REG_H_M", 0x46, READ),
+        ("OFFSET_Y_REG_L_M", 0x47, READ),
+        ("OFFSET_Y_REG_H_M", 0x48, READ),
+        ("OFFSET_Z_REG_L_M", 0x49, READ),
+        ("OFFSET_Z_REG_H_M", 0x4A, READ),
+        ("WHO_AM_I_M", 0x4F, READ),
+        ("CFG_REG_A_M", 0x60, READ),
+        ("CFG_REG_B_M", 0x61, READ),
+        ("CFG_REG_C_M", 0x62, READ),
+        ("INT_CRTL_REG_M", 0x63, READ),
+        // Clears a latched interrupt.
+        ("INT_SOURCE_REG_M", 0x64, SKIP),
+        ("INT_THS_L_REG_M", 0x65, READ),
+        ("INT_THS_H_REG_M", 0x66, READ),
+        ("STATUS_REG_M", 0x67, READ),
+        // Clears the data-ready flag and consumes a one-shot measurement.
+        ("OUTX_L_REG_M", 0x68, SKIP),
+        ("OUTX_H_REG_M", 0x69, SKIP),
+        ("OUTY_L_REG_M", 0x6A, SKIP),
+        ("OUTY_H_REG_M", 0x6B, SKIP),
+        ("OUTZ_L_REG_M", 0x6C, SKIP),
+        ("OUTZ_H_REG_M", 0x6D, SKIP),
+    ]
+);
+
+/// Values of all accelerometer and magnetometer registers which can be read
+/// without side effects.
+///
+/// The [`Debug`](fmt::Debug) output lists every register by name, e.g. for
+/// attaching it to a bug report. Registers which were skipped are listed as
+/// `not read`.
+///
+/// See [`Lsm303agr::dump_device`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct DeviceSnapshot {
+    accel: [Option<u8>; ACCEL_REGISTERS.len()],
+    mag: [Option<u8>; MAG_REGISTERS.len()],
+}
+
+impl DeviceSnapshot {
+    /// Get the value of the accelerometer register at `address`.
+    ///
+    /// Returns `None` if the register is not readable or was not read.
+    pub fn accel_register(&self, address: u8) -> Option<u8> {
+        register_value(ACCEL_REGISTERS, &self.accel, address)
+    }
+
+    /// Get the value of the magnetometer register at `address`.
+    ///
+    /// Returns `None` if the register is not readable or was not read.
+    pub fn mag_register(&self, address: u8) -> Option<u8> {
+        register_value(MAG_REGISTERS, &self.mag, address)
+    }
+}
+
+struct NotRead;
+
+impl fmt::Debug for NotRead {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("not read")
+    }
+}
+
+impl fmt::Debug for DeviceSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct("DeviceSnapshot");
+
+        let accel = ACCEL_REGISTERS.iter().zip(self.accel.iter());
+        let mag = MAG_REGISTERS.iter().zip(self.mag.iter());
+        for ((name, _, _), value) in accel.chain(mag) {
+            match value {
+                Some(value) => s.field(name, value),
+                None => s.field(name, &NotRead),
+            };
+        }
+
+        s.finish()
+    }
+}
+
+fn register_value(
+    registers: &[(&str, u8, bool)],
+    values: &[Option<u8>],
+    address: u8,
+) -> Option<u8> {
+    registers
+        .iter()
+        .position(|&(_, addr, _)| addr == address)
+        .and_then(|i| values[i])
+}
+
+impl<DI, CommE, PinE, MODE> Lsm303agr<DI, MODE>
+where
+    DI: ReadData<Error = Error<CommE, PinE>>,
+{
+    /// Read all accelerometer and magnetometer registers which can be read
+    /// without side effects.
+    ///
+    /// This is meant for diagnostics, e.g. for attaching the [`Debug`](fmt::Debug)
+    /// output of the returned snapshot to a bug report.
+    ///
+    /// The following registers are skipped, so dumping the device does not
+    /// change its state:
+    /// - `REFERENCE_A`, reading it resets the high-pass filter.
+    /// - `OUT_X_L_A` to `OUT_Z_H_A`, reading them removes a sample from the FIFO.
+    /// - `OUTX_L_REG_M` to `OUTZ_H_REG_M`, reading them clears the data-ready flag
+    ///   and consumes the result of a one-shot measurement.
+    /// - `INT1_SRC_A`, `INT2_SRC_A`, `CLICK_SRC_A` and `INT_SOURCE_REG_M`,
+    ///   reading them clears latched interrupts.
+    ///
+    /// Each register is read in a separate transfer.
+    pub fn dump_device(&mut self) -> Result<DeviceSnapshot, Error<CommE, PinE>> {
+        let mut snapshot = DeviceSnapshot {
+            accel: [None; ACCEL_REGISTERS.len()],
+            mag: [None; MAG_REGISTERS.len()],
+        };
+
+        for (&(_, address, read), value) in ACCEL_REGISTERS.iter().zip(snapshot.accel.iter_mut()) {
+            if read {
+                *value = Some(read_accel(&mut self.iface, address)?);
+            }
+        }
+        for (&(_, address, read), value) in MAG_REGISTERS.iter().zip(snapshot.mag.iter_mut()) {
+            if read {
+                *value = Some(read_mag(&mut self.iface, address)?);
+            }
+        }
+
+        Ok(snapshot)
+    }
+}
@@ -1,6 +1,13 @@
+use core::{
+    cmp::Ordering,
+    convert::TryFrom,
+    fmt,
+    ops::{Add, Sub},
+};
+
 use bitflags::bitflags;
 
-use crate::register_address::{RegRead, StatusRegAuxA, WhoAmIA, WhoAmIM};
+use crate::register_address::{IntSourceRegM, RegRead, StatusRegAuxA, WhoAmIA, WhoAmIM};
 
 /// All possible errors in this crate
 #[derive(Debug)]
@@ -11,6 +18,9 @@ pub enum Error<CommE, PinE> {
     Pin(PinE),
     /// Invalid input data provided
     InvalidInputData,
+    /// Register contents did not match after writing, see
+    /// [`Lsm303agr::set_write_verification`](crate::Lsm303agr::set_write_verification)
+    WriteVerification,
 }
 
 /// All possible errors in this crate
@@ -22,6 +32,48 @@ pub struct ModeChangeError<CommE, PinE, DEV> {
     pub dev: DEV,
 }
 
+impl<CommE, PinE> fmt::Display for Error<CommE, PinE>
+where
+    CommE: fmt::Debug,
+    PinE: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Comm(e) => write!(f, "communication error: {:?}", e),
+            Self::Pin(e) => write!(f, "chip-select pin error: {:?}", e),
+            Self::InvalidInputData => write!(f, "invalid input data"),
+            Self::WriteVerification => write!(f, "register write verification failed"),
+        }
+    }
+}
+
+#[cfg(feature = "core-error")]
+impl<CommE, PinE> core::error::Error for Error<CommE, PinE>
+where
+    CommE: fmt::Debug,
+    PinE: fmt::Debug,
+{
+}
+
+impl<CommE, PinE, DEV> fmt::Display for ModeChangeError<CommE, PinE, DEV>
+where
+    CommE: fmt::Debug,
+    PinE: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "magnetometer mode change failed: {}", self.error)
+    }
+}
+
+#[cfg(feature = "core-error")]
+impl<CommE, PinE, DEV> core::error::Error for ModeChangeError<CommE, PinE, DEV>
+where
+    CommE: fmt::Debug,
+    PinE: fmt::Debug,
+    DEV: fmt::Debug,
+{
+}
+
 /// Device operation modes
 pub mod mode {
     /// Marker type for magnetometer in one-shot (single) mode.
@@ -30,6 +82,22 @@ pub mod mode {
     /// Marker type for magnetometer in continuous mode.
     #[derive(Debug)]
     pub enum MagContinuous {}
+
+    /// Magnetometer mode marker.
+    ///
+    /// This trait is sealed and only implemented for [`MagOneShot`] and [`MagContinuous`].
+    pub trait MagModeMarker: crate::private::Sealed {
+        /// Whether this is the continuous mode marker.
+        const IS_CONTINUOUS: bool;
+    }
+
+    impl MagModeMarker for MagOneShot {
+        const IS_CONTINUOUS: bool = false;
+    }
+
+    impl MagModeMarker for MagContinuous {
+        const IS_CONTINUOUS: bool = true;
+    }
 }
 
 /// An Accelerometer ID.
@@ -54,7 +122,20 @@ impl AccelerometerId {
     }
 }
 
+/// A measurement axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis {
+    /// X-axis
+    X,
+    /// Y-axis
+    Y,
+    /// Z-axis
+    Z,
+}
+
 /// An acceleration measurement.
+///
+/// It is [displayed](fmt::Display) in m*g*, e.g. as `x=12mg y=-3mg z=998mg`.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Acceleration {
     pub(crate) x: u16,
@@ -77,6 +158,40 @@ impl RegRead<(u16, u16, u16)> for Acceleration {
 }
 
 impl Acceleration {
+    /// Create a zero acceleration measurement with the given `mode` and `scale`.
+    ///
+    /// This is useful e.g. as the initial value of a filter which combines it with
+    /// measurements read using the same `mode` and `scale`.
+    #[inline]
+    pub const fn zero(mode: AccelMode, scale: AccelScale) -> Self {
+        Self {
+            x: 0,
+            y: 0,
+            z: 0,
+            mode,
+            scale,
+        }
+    }
+
+    /// Create an acceleration measurement from the raw output register bytes.
+    ///
+    /// The `bytes` are the contents of `OUT_X_L_A` to `OUT_Z_H_A` in register order,
+    /// with the default little-endian byte order, i.e. the low byte of each axis first.
+    /// The `mode` and `scale` must be the ones the sample was measured with.
+    ///
+    /// This allows decoding samples which were recorded elsewhere using the same
+    /// scaling as a live read.
+    #[inline]
+    pub const fn from_raw(bytes: [u8; 6], mode: AccelMode, scale: AccelScale) -> Self {
+        Self {
+            x: u16::from_le_bytes([bytes[0], bytes[1]]),
+            y: u16::from_le_bytes([bytes[2], bytes[3]]),
+            z: u16::from_le_bytes([bytes[4], bytes[5]]),
+            mode,
+            scale,
+        }
+    }
+
     /// Raw acceleration in X-direction.
     #[inline]
     pub const fn x_raw(&self) -> u16 {
@@ -101,6 +216,52 @@ impl Acceleration {
         (self.x, self.y, self.z)
     }
 
+    /// Left-justified acceleration in X-direction.
+    ///
+    /// This is the signed value as presented by the device, i.e. the 8-bit (low-power),
+    /// 10-bit (normal) or 12-bit (high-resolution) measurement is stored in the upper
+    /// bits. In contrast, [`x_raw`](Acceleration::x_raw) returns the same bits unsigned
+    /// and [`x_unscaled`](Acceleration::x_unscaled) returns the right-justified value.
+    ///
+    /// ```
+    /// use lsm303agr::{AccelMode, AccelScale, Acceleration};
+    ///
+    /// // OUT_X_L_A = 0xC0, OUT_X_H_A = 0xFF in normal (10-bit) mode
+    /// let data = Acceleration::from_raw([0xC0, 0xFF, 0, 0, 0, 0], AccelMode::Normal, AccelScale::G2);
+    ///
+    /// assert_eq!(data.x_raw(), 0xFFC0);
+    /// assert_eq!(data.x_left_justified(), -64);
+    /// assert_eq!(data.x_unscaled(), -1);
+    /// ```
+    #[inline]
+    pub const fn x_left_justified(&self) -> i16 {
+        self.x as i16
+    }
+
+    /// Left-justified acceleration in Y-direction.
+    ///
+    /// See [`x_left_justified`](Acceleration::x_left_justified).
+    #[inline]
+    pub const fn y_left_justified(&self) -> i16 {
+        self.y as i16
+    }
+
+    /// Left-justified acceleration in Z-direction.
+    ///
+    /// See [`x_left_justified`](Acceleration::x_left_justified).
+    #[inline]
+    pub const fn z_left_justified(&self) -> i16 {
+        self.z as i16
+    }
+
+    /// Left-justified acceleration in X-, Y- and Z-directions.
+    ///
+    /// See [`x_left_justified`](Acceleration::x_left_justified).
+    #[inline]
+    pub const fn xyz_left_justified(&self) -> (i16, i16, i16) {
+        (self.x as i16, self.y as i16, self.z as i16)
+    }
+
     /// Unscaled acceleration in X-direction.
     #[inline]
     pub const fn x_unscaled(&self) -> i16 {
@@ -131,6 +292,38 @@ impl Acceleration {
         )
     }
 
+    /// Whether the acceleration in X-direction is at the limit of the full scale.
+    #[inline]
+    pub const fn x_saturated(&self) -> bool {
+        self.is_saturated_unscaled(self.x_unscaled())
+    }
+
+    /// Whether the acceleration in Y-direction is at the limit of the full scale.
+    #[inline]
+    pub const fn y_saturated(&self) -> bool {
+        self.is_saturated_unscaled(self.y_unscaled())
+    }
+
+    /// Whether the acceleration in Z-direction is at the limit of the full scale.
+    #[inline]
+    pub const fn z_saturated(&self) -> bool {
+        self.is_saturated_unscaled(self.z_unscaled())
+    }
+
+    /// Whether the acceleration in any direction is at the limit of the full scale.
+    ///
+    /// If this is the case, the actual acceleration may exceed the measured value
+    /// and a larger [`AccelScale`] should be selected.
+    #[inline]
+    pub const fn is_saturated(&self) -> bool {
+        self.x_saturated() || self.y_saturated() || self.z_saturated()
+    }
+
+    const fn is_saturated_unscaled(&self, unscaled: i16) -> bool {
+        let resolution_factor = self.mode.resolution_factor();
+        unscaled >= i16::MAX / resolution_factor || unscaled <= i16::MIN / resolution_factor
+    }
+
     /// Acceleration in X-direction in m*g* (milli-*g*).
     #[inline]
     pub const fn x_mg(&self) -> i32 {
@@ -161,6 +354,70 @@ impl Acceleration {
             (z_unscaled as i32) * scaling_factor,
         )
     }
+
+    /// Acceleration in X-, Y- and Z-directions as [`MilliG`].
+    #[inline]
+    pub const fn xyz_milli_g(&self) -> (MilliG, MilliG, MilliG) {
+        let (x, y, z) = self.xyz_mg();
+        (MilliG(x), MilliG(y), MilliG(z))
+    }
+
+    /// Acceleration in X-, Y- and Z-directions in the given [`AccelUnit`].
+    #[inline]
+    pub fn xyz_in<U: AccelUnit>(&self) -> (U, U, U) {
+        let (x, y, z) = self.xyz_mg();
+        (U::from_milli_g(x), U::from_milli_g(y), U::from_milli_g(z))
+    }
+
+    /// Pitch angle in degrees, i.e. the rotation around the Y-axis.
+    ///
+    /// The X-axis points forward and the Z-axis up, so the pitch is positive when
+    /// the front of the device is raised. Both pitch and roll are 0° when the device
+    /// lies flat. The angle is in the range [-90°, 90°].
+    ///
+    /// This is only available with the `math` feature.
+    #[cfg(feature = "math")]
+    pub fn pitch_deg(&self) -> f32 {
+        let (x, y, z) = self.xyz_mg();
+        let (x, y, z) = (x as f32, y as f32, z as f32);
+        crate::math::to_degrees(crate::math::atan2(x, crate::math::sqrt(y * y + z * z)))
+    }
+
+    /// Roll angle in degrees, i.e. the rotation around the X-axis.
+    ///
+    /// The Y-axis points left and the Z-axis up, so the roll is positive when
+    /// the left side of the device is raised. The angle is in the range [-180°, 180°].
+    ///
+    /// This is only available with the `math` feature.
+    #[cfg(feature = "math")]
+    pub fn roll_deg(&self) -> f32 {
+        let (_, y, z) = self.xyz_mg();
+        crate::math::to_degrees(crate::math::atan2(y as f32, z as f32))
+    }
+
+    /// Acceleration in X-, Y- and Z-directions in mm/s² (milli-meters per second squared).
+    ///
+    /// This is computed using integer math only.
+    #[inline]
+    pub const fn xyz_mm_per_s2(&self) -> (i32, i32, i32) {
+        let (x, y, z) = self.xyz_mg();
+
+        (mg_to_mm_per_s2(x), mg_to_mm_per_s2(y), mg_to_mm_per_s2(z))
+    }
+}
+
+impl fmt::Display for Acceleration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (x, y, z) = self.xyz_mg();
+        write!(f, "x={}mg y={}mg z={}mg", x, y, z)
+    }
+}
+
+/// Standard gravity in µm/s².
+const STANDARD_GRAVITY_UM_PER_S2: i64 = 9_806_650;
+
+const fn mg_to_mm_per_s2(mg: i32) -> i32 {
+    (mg as i64 * STANDARD_GRAVITY_UM_PER_S2 / 1_000_000) as i32
 }
 
 /// A Magnetometer ID.
@@ -186,6 +443,10 @@ impl MagnetometerId {
 }
 
 /// A magnetic field measurement.
+///
+/// The [`Default`] value is a zero magnetic field, e.g. for initializing a buffer or a filter.
+///
+/// It is [displayed](fmt::Display) in nT, e.g. as `x=150nT y=-300nT z=45000nT`.
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub struct MagneticField {
     pub(crate) x: u16,
@@ -208,6 +469,20 @@ impl RegRead<(u16, u16, u16)> for MagneticField {
 impl MagneticField {
     const SCALING_FACTOR: i32 = 150;
 
+    /// Create a magnetic field measurement from the raw output register bytes.
+    ///
+    /// The `bytes` are the contents of `OUTX_L_REG_M` to `OUTZ_H_REG_M` in register
+    /// order, with the default little-endian byte order, i.e. the low byte of each
+    /// axis first.
+    #[inline]
+    pub const fn from_raw(bytes: [u8; 6]) -> Self {
+        Self {
+            x: u16::from_le_bytes([bytes[0], bytes[1]]),
+            y: u16::from_le_bytes([bytes[2], bytes[3]]),
+            z: u16::from_le_bytes([bytes[4], bytes[5]]),
+        }
+    }
+
     /// Raw magnetic field in X-direction.
     #[inline]
     pub const fn x_raw(&self) -> u16 {
@@ -279,10 +554,115 @@ impl MagneticField {
     pub const fn xyz_nt(&self) -> (i32, i32, i32) {
         (self.x_nt(), self.y_nt(), self.z_nt())
     }
+
+    /// Magnetic field in X-, Y- and Z-directions as [`NanoTesla`].
+    #[inline]
+    pub const fn xyz_nano_tesla(&self) -> (NanoTesla, NanoTesla, NanoTesla) {
+        (
+            NanoTesla(self.x_nt()),
+            NanoTesla(self.y_nt()),
+            NanoTesla(self.z_nt()),
+        )
+    }
+}
+
+impl fmt::Display for MagneticField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (x, y, z) = self.xyz_nt();
+        write!(f, "x={}nT y={}nT z={}nT", x, y, z)
+    }
+}
+
+/// An acceleration in m*g* (milli-*g*).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MilliG(pub i32);
+
+/// A magnetic field strength in nT (nano-Tesla).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct NanoTesla(pub i32);
+
+macro_rules! impl_unit {
+    ($ty:ident) => {
+        impl Add for $ty {
+            type Output = Self;
+
+            #[inline]
+            fn add(self, rhs: Self) -> Self::Output {
+                Self(self.0 + rhs.0)
+            }
+        }
+
+        impl Sub for $ty {
+            type Output = Self;
+
+            #[inline]
+            fn sub(self, rhs: Self) -> Self::Output {
+                Self(self.0 - rhs.0)
+            }
+        }
+
+        impl From<$ty> for i32 {
+            #[inline]
+            fn from(value: $ty) -> Self {
+                value.0
+            }
+        }
+    };
+}
+
+impl_unit!(MilliG);
+impl_unit!(NanoTesla);
+
+/// An acceleration in *g*.
+///
+/// This is only available with the `float` feature.
+#[cfg(feature = "float")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct G(pub f32);
+
+/// An acceleration in m/s² (meters per second squared).
+///
+/// This is only available with the `float` feature.
+#[cfg(feature = "float")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub struct MeterPerSecSquared(pub f32);
+
+/// A unit of acceleration.
+///
+/// This allows selecting the unit using a type parameter, e.g. with
+/// [`Acceleration::xyz_in`] and [`Lsm303agr::acceleration_in`](crate::Lsm303agr::acceleration_in).
+pub trait AccelUnit {
+    /// Convert from m*g* (milli-*g*).
+    fn from_milli_g(mg: i32) -> Self;
+}
+
+impl AccelUnit for MilliG {
+    #[inline]
+    fn from_milli_g(mg: i32) -> Self {
+        Self(mg)
+    }
+}
+
+#[cfg(feature = "float")]
+impl AccelUnit for G {
+    #[inline]
+    fn from_milli_g(mg: i32) -> Self {
+        Self(mg as f32 / 1000.0)
+    }
+}
+
+#[cfg(feature = "float")]
+impl AccelUnit for MeterPerSecSquared {
+    #[inline]
+    fn from_milli_g(mg: i32) -> Self {
+        Self(mg as f32 * (STANDARD_GRAVITY_UM_PER_S2 as f32 / 1_000_000_000.0))
+    }
 }
 
 /// Accelerometer output data rate
-#[derive(Debug, Clone, Copy, PartialEq)]
+///
+/// Output data rates are ordered by their frequency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum AccelOutputDataRate {
     /// 1 Hz (all modes)
     Hz1,
@@ -307,6 +687,20 @@ pub enum AccelOutputDataRate {
 }
 
 impl AccelOutputDataRate {
+    /// All output data rates, ordered by frequency.
+    pub const ALL: &'static [Self] = &[
+        Self::Hz1,
+        Self::Hz10,
+        Self::Hz25,
+        Self::Hz50,
+        Self::Hz100,
+        Self::Hz200,
+        Self::Hz400,
+        Self::Khz1_344,
+        Self::Khz1_620LowPower,
+        Self::Khz5_376LowPower,
+    ];
+
     /// Create an `AccelOutputDataRate` with the given frequency in Hertz.
     pub const fn from_hertz(hz: u16) -> Option<Self> {
         Some(match hz {
@@ -324,41 +718,57 @@ impl AccelOutputDataRate {
         })
     }
 
-    /// 1/ODR ms
+    /// Get the frequency in Hertz.
+    pub const fn to_hertz(self) -> u16 {
+        match self {
+            Self::Hz1 => 1,
+            Self::Hz10 => 10,
+            Self::Hz25 => 25,
+            Self::Hz50 => 50,
+            Self::Hz100 => 100,
+            Self::Hz200 => 200,
+            Self::Hz400 => 400,
+            Self::Khz1_344 => 1344,
+            Self::Khz1_620LowPower => 1620,
+            Self::Khz5_376LowPower => 5376,
+        }
+    }
+
+    /// 1/ODR µs, rounded up
     pub(crate) const fn turn_on_time_us_frac_1(&self) -> u32 {
         match self {
-            Self::Hz1 => 1000,
-            Self::Hz10 => 100,
-            Self::Hz25 => 40,
-            Self::Hz50 => 20,
-            Self::Hz100 => 10,
-            Self::Hz200 => 5,
-            Self::Hz400 => 3,            //  2.5
-            Self::Khz1_344 => 1,         // ~0.7
-            Self::Khz1_620LowPower => 1, // ~0.6
-            Self::Khz5_376LowPower => 1, // ~0.2
+            Self::Hz1 => 1_000_000,
+            Self::Hz10 => 100_000,
+            Self::Hz25 => 40_000,
+            Self::Hz50 => 20_000,
+            Self::Hz100 => 10_000,
+            Self::Hz200 => 5_000,
+            Self::Hz400 => 2_500,
+            Self::Khz1_344 => 745,         // ~744.0
+            Self::Khz1_620LowPower => 618, // ~617.3
+            Self::Khz5_376LowPower => 187, // ~186.0
         }
     }
 
-    /// 7/ODR ms
+    /// 7/ODR µs, rounded up
     pub(crate) const fn turn_on_time_us_frac_7(&self) -> u32 {
         match self {
-            Self::Hz1 => 7000,
-            Self::Hz10 => 700,
-            Self::Hz25 => 280,
-            Self::Hz50 => 140,
-            Self::Hz100 => 70,
-            Self::Hz200 => 35,
-            Self::Hz400 => 18,           // 17.5
-            Self::Khz1_344 => 6,         // ~5.2
-            Self::Khz1_620LowPower => 5, // ~4.3
-            Self::Khz5_376LowPower => 2, // ~1.3
+            Self::Hz1 => 7_000_000,
+            Self::Hz10 => 700_000,
+            Self::Hz25 => 280_000,
+            Self::Hz50 => 140_000,
+            Self::Hz100 => 70_000,
+            Self::Hz200 => 35_000,
+            Self::Hz400 => 17_500,
+            Self::Khz1_344 => 5_209,         // ~5208.3
+            Self::Khz1_620LowPower => 4_321, // ~4321.0
+            Self::Khz5_376LowPower => 1_303, // ~1302.1
         }
     }
 }
 
 /// Accelerometer mode
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum AccelMode {
     /// Power down
     PowerDown,
@@ -413,7 +823,7 @@ impl AccelMode {
 }
 
 /// Accelerometer scaling factor
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum AccelScale {
     /// Plus or minus 2g
     G2 = 2,
@@ -425,8 +835,57 @@ pub enum AccelScale {
     G16 = 16,
 }
 
+impl AccelScale {
+    /// Create an `AccelScale` with the given full scale in *g*.
+    pub const fn from_g(g: u8) -> Option<Self> {
+        Some(match g {
+            2 => Self::G2,
+            4 => Self::G4,
+            8 => Self::G8,
+            16 => Self::G16,
+            _ => return None,
+        })
+    }
+
+    /// Get the full scale in *g*.
+    pub const fn to_g(self) -> u8 {
+        self as u8
+    }
+}
+
+/// Accelerometer high-pass filter mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccelHighPassMode {
+    /// Normal mode (reset by reading the `REFERENCE/DATACAPTURE_A` register)
+    NormalWithReset,
+    /// Reference signal for filtering
+    Reference,
+    /// Normal mode
+    Normal,
+    /// Autoreset on interrupt event
+    AutoResetOnInterrupt,
+}
+
+/// Accelerometer high-pass filter cut-off frequency
+///
+/// The cut-off frequency depends on the accelerometer output data rate,
+/// the ratios given here are approximate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccelHighPassCutoff {
+    /// About ODR/50
+    OdrDiv50,
+    /// About ODR/100
+    OdrDiv100,
+    /// About ODR/200
+    OdrDiv200,
+    /// About ODR/400
+    OdrDiv400,
+}
+
 /// Magnetometer output data rate
-#[derive(Debug, Clone, Copy, PartialEq)]
+///
+/// Output data rates are ordered by their frequency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MagOutputDataRate {
     /// 10 Hz
     Hz10,
@@ -439,6 +898,9 @@ pub enum MagOutputDataRate {
 }
 
 impl MagOutputDataRate {
+    /// All output data rates, ordered by frequency.
+    pub const ALL: &'static [Self] = &[Self::Hz10, Self::Hz20, Self::Hz50, Self::Hz100];
+
     /// Create an `MagOutputDataRate` with the given frequency in Hertz.
     pub const fn from_hertz(hz: u16) -> Option<Self> {
         Some(match hz {
@@ -450,19 +912,29 @@ impl MagOutputDataRate {
         })
     }
 
-    /// 1/ODR ms
+    /// Get the frequency in Hertz.
+    pub const fn to_hertz(self) -> u16 {
+        match self {
+            Self::Hz10 => 10,
+            Self::Hz20 => 20,
+            Self::Hz50 => 50,
+            Self::Hz100 => 100,
+        }
+    }
+
+    /// 1/ODR µs
     pub(crate) const fn turn_on_time_us_frac_1(&self) -> u32 {
         match self {
-            Self::Hz10 => 100,
-            Self::Hz20 => 50,
-            Self::Hz50 => 20,
-            Self::Hz100 => 10,
+            Self::Hz10 => 100_000,
+            Self::Hz20 => 50_000,
+            Self::Hz50 => 20_000,
+            Self::Hz100 => 10_000,
         }
     }
 }
 
 /// Magnetometer mode
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MagMode {
     /// Low-power mode
     LowPower,
@@ -583,26 +1055,43 @@ impl TemperatureStatus {
     }
 }
 
+/// Source of the temperature data.
+///
+/// The LSM303AGR has no auxiliary ADC sharing the temperature channel, so
+/// temperature data is available whenever the temperature sensor is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TempSource {
+    /// The temperature sensor is disabled, no temperature data is available.
+    Disabled,
+    /// The temperature sensor is enabled.
+    Sensor,
+}
+
 /// A temperature measurement.
+///
+/// It is [displayed](fmt::Display) in °C rounded to one decimal place, e.g. as `25.3°C`.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Temperature {
     pub(crate) raw: u16,
+    pub(crate) mode: AccelMode,
 }
 
 impl RegRead<u16> for Temperature {
-    type Output = Self;
+    type Output = u16;
 
     /// OUT_TEMP_L_A
     const ADDR: u8 = 0x0C;
 
-    #[inline]
+    #[inline(always)]
     fn from_data(data: u16) -> Self::Output {
-        Temperature { raw: data }
+        data
     }
 }
 
 impl Temperature {
+    #[cfg(feature = "float")]
     const DEFAULT: f32 = 25.0;
+    const DEFAULT_MILLI: i32 = 25_000;
 
     /// Raw temperature.
     #[inline]
@@ -610,21 +1099,61 @@ impl Temperature {
         self.raw
     }
 
+    /// Resolution of the temperature measurement in bits.
+    ///
+    /// The temperature sensor has a resolution of 8 bits in low-power mode and
+    /// 10 bits in normal and high-resolution mode. The remaining lower bits of
+    /// the [`unscaled`](Temperature::unscaled) value carry no information.
+    ///
+    /// Returns 0 if the accelerometer was powered down, in which case the
+    /// temperature is not being measured.
+    #[inline]
+    pub const fn resolution_bits(&self) -> u8 {
+        match self.mode {
+            AccelMode::PowerDown => 0,
+            AccelMode::LowPower => 8,
+            AccelMode::Normal | AccelMode::HighResolution => 10,
+        }
+    }
+
     /// Unscaled temperature.
+    ///
+    /// The temperature is a left-justified two's complement value, i.e. the
+    /// upper byte is the temperature difference to 25 °C in °C.
     #[inline]
     pub const fn unscaled(&self) -> i16 {
         self.raw as i16
     }
 
     /// Temperature in °C.
+    ///
+    /// This is only available with the `float` feature.
+    #[cfg(feature = "float")]
     #[inline]
     pub fn degrees_celsius(&self) -> f32 {
         (self.unscaled() as f32) / 256.0 + Self::DEFAULT
     }
+
+    /// Temperature in m°C (milli-degrees Celsius).
+    ///
+    /// This is computed using integer math only.
+    #[inline]
+    pub const fn degrees_celsius_milli(&self) -> i32 {
+        (self.unscaled() as i32) * 1000 / 256 + Self::DEFAULT_MILLI
+    }
+}
+
+impl fmt::Display for Temperature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let milli = self.degrees_celsius_milli();
+        let sign = if milli < 0 { "-" } else { "" };
+        let tenths = (milli.unsigned_abs() + 50) / 100;
+        write!(f, "{}{}.{}°C", sign, tenths / 10, tenths % 10)
+    }
 }
 
 /// A FIFO mode.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum FifoMode {
     /// Bypass mode
     Bypass,
@@ -636,8 +1165,46 @@ pub enum FifoMode {
     StreamToFifo,
 }
 
-/// An interrupt.
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// An interrupt pin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InterruptLine {
+    /// INT1 pin
+    Int1,
+    /// INT2 pin
+    Int2,
+}
+
+/// An accelerometer interrupt source.
+///
+/// Use together with an [`InterruptLine`] to select the pin the interrupt is routed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InterruptSource {
+    /// AOI1 interrupt (INT1 and INT2 pins)
+    Aoi1,
+    /// AOI2 interrupt (INT1 and INT2 pins)
+    Aoi2,
+    /// CLICK interrupt (INT1 and INT2 pins)
+    Click,
+    /// DRDY1 interrupt (only INT1 pin)
+    DataReady1,
+    /// DRDY2 interrupt (only INT1 pin)
+    DataReady2,
+    /// FIFO overrun interrupt (only INT1 pin)
+    FifoOverrun,
+    /// FIFO watermark interrupt (only INT1 pin)
+    FifoWatermark,
+    /// Activity interrupt (only INT2 pin)
+    Activity,
+    /// Boot status (only INT2 pin)
+    Boot,
+}
+
+/// An interrupt on the INT1 pin.
+///
+/// This converts into an [`InterruptLine::Int1`] and [`InterruptSource`] pair,
+/// so it can be passed directly to [`Lsm303agr::acc_enable_interrupt`](crate::Lsm303agr::acc_enable_interrupt)
+/// and [`Lsm303agr::acc_disable_interrupt`](crate::Lsm303agr::acc_disable_interrupt).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Interrupt {
     /// AOI1 interrupt on INT1 pin.
     Aoi1,
@@ -654,3 +1221,368 @@ pub enum Interrupt {
     /// FIFO watermark interrupt on INT1 pin.
     FifoWatermark,
 }
+
+impl From<Interrupt> for (InterruptLine, InterruptSource) {
+    fn from(interrupt: Interrupt) -> Self {
+        let source = match interrupt {
+            Interrupt::Aoi1 => InterruptSource::Aoi1,
+            Interrupt::Aoi2 => InterruptSource::Aoi2,
+            Interrupt::Click => InterruptSource::Click,
+            Interrupt::DataReady1 => InterruptSource::DataReady1,
+            Interrupt::DataReady2 => InterruptSource::DataReady2,
+            Interrupt::FifoOverrun => InterruptSource::FifoOverrun,
+            Interrupt::FifoWatermark => InterruptSource::FifoWatermark,
+        };
+
+        (InterruptLine::Int1, source)
+    }
+}
+
+/// Accelerometer interrupt generator configuration.
+///
+/// The `threshold` is given in LSB, where 1 LSB is 16 m*g* at ±2*g*, 32 m*g* at ±4*g*,
+/// 62 m*g* at ±8*g* and 186 m*g* at ±16*g*. The `duration` is given in 1/ODR steps.
+/// Both must be in the range \[0, 127\].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct AccelInterruptConfig {
+    /// Combine the enabled events with AND instead of OR.
+    pub and_combination: bool,
+    /// Enable 6-direction detection.
+    pub six_direction: bool,
+    /// Enable interrupt on X-axis high event.
+    pub x_high: bool,
+    /// Enable interrupt on X-axis low event.
+    pub x_low: bool,
+    /// Enable interrupt on Y-axis high event.
+    pub y_high: bool,
+    /// Enable interrupt on Y-axis low event.
+    pub y_low: bool,
+    /// Enable interrupt on Z-axis high event.
+    pub z_high: bool,
+    /// Enable interrupt on Z-axis low event.
+    pub z_low: bool,
+    /// Interrupt threshold.
+    pub threshold: u8,
+    /// Minimum event duration.
+    pub duration: u8,
+}
+
+/// Accelerometer click detection configuration
+///
+/// The `threshold` is converted to LSB using the current scale, where
+/// 1 LSB = full scale / 128, e.g. 15.625 m*g* at ±2*g*. The time values are given
+/// in µs and converted to LSB using the current output data rate, where
+/// 1 LSB = 1/ODR.
+///
+/// After conversion, the threshold and time limit must be in the range \[0, 127\],
+/// and the sum of time latency and time window must be in the range \[0, 255\].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct AccelClickConfig {
+    /// Enable single click detection on the X-axis.
+    pub x_single: bool,
+    /// Enable double click detection on the X-axis.
+    pub x_double: bool,
+    /// Enable single click detection on the Y-axis.
+    pub y_single: bool,
+    /// Enable double click detection on the Y-axis.
+    pub y_double: bool,
+    /// Enable single click detection on the Z-axis.
+    pub z_single: bool,
+    /// Enable double click detection on the Z-axis.
+    pub z_double: bool,
+    /// Latch the click interrupt until the click source is read.
+    pub latched: bool,
+    /// Click threshold.
+    pub threshold: MilliG,
+    /// Maximum time in µs the acceleration may exceed the threshold to be detected as a click.
+    pub time_limit_us: u32,
+    /// Time in µs after the first click before the second click can be detected.
+    pub time_latency_us: u32,
+    /// Time in µs after the latency during which the second click can be detected.
+    pub time_window_us: u32,
+}
+
+bitflags! {
+    #[derive(Default)]
+    pub struct AccelInterruptFlags: u8 {
+        const IA = 0b01000000;
+        const ZH = 0b00100000;
+        const ZL = 0b00010000;
+        const YH = 0b00001000;
+        const YL = 0b00000100;
+        const XH = 0b00000010;
+        const XL = 0b00000001;
+    }
+}
+
+/// Accelerometer interrupt generator source
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct AccelInterruptSource {
+    flags: AccelInterruptFlags,
+}
+
+impl AccelInterruptSource {
+    pub(crate) const fn new(flags: AccelInterruptFlags) -> Self {
+        Self { flags }
+    }
+
+    /// One or more interrupt events have been generated.
+    #[inline]
+    pub const fn active(&self) -> bool {
+        self.flags.contains(AccelInterruptFlags::IA)
+    }
+
+    /// X-axis high event occurred.
+    #[inline]
+    pub const fn x_high(&self) -> bool {
+        self.flags.contains(AccelInterruptFlags::XH)
+    }
+
+    /// X-axis low event occurred.
+    #[inline]
+    pub const fn x_low(&self) -> bool {
+        self.flags.contains(AccelInterruptFlags::XL)
+    }
+
+    /// Y-axis high event occurred.
+    #[inline]
+    pub const fn y_high(&self) -> bool {
+        self.flags.contains(AccelInterruptFlags::YH)
+    }
+
+    /// Y-axis low event occurred.
+    #[inline]
+    pub const fn y_low(&self) -> bool {
+        self.flags.contains(AccelInterruptFlags::YL)
+    }
+
+    /// Z-axis high event occurred.
+    #[inline]
+    pub const fn z_high(&self) -> bool {
+        self.flags.contains(AccelInterruptFlags::ZH)
+    }
+
+    /// Z-axis low event occurred.
+    #[inline]
+    pub const fn z_low(&self) -> bool {
+        self.flags.contains(AccelInterruptFlags::ZL)
+    }
+}
+
+/// Magnetometer interrupt configuration.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct MagInterruptConfig {
+    /// Enable interrupt recognition on the X-axis.
+    pub x_enabled: bool,
+    /// Enable interrupt recognition on the Y-axis.
+    pub y_enabled: bool,
+    /// Enable interrupt recognition on the Z-axis.
+    pub z_enabled: bool,
+    /// Interrupt signal is active-high instead of active-low.
+    pub active_high: bool,
+    /// Interrupt is latched until the interrupt source is read instead of pulsed.
+    pub latched: bool,
+    /// Enable the interrupt.
+    pub enabled: bool,
+}
+
+/// Magnetometer interrupt source
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct MagInterruptSource {
+    flags: IntSourceRegM,
+}
+
+impl MagInterruptSource {
+    pub(crate) const fn new(flags: IntSourceRegM) -> Self {
+        Self { flags }
+    }
+
+    /// X-axis value exceeds the threshold on the positive side.
+    #[inline]
+    pub const fn x_positive(&self) -> bool {
+        self.flags.contains(IntSourceRegM::PTH_X)
+    }
+
+    /// Y-axis value exceeds the threshold on the positive side.
+    #[inline]
+    pub const fn y_positive(&self) -> bool {
+        self.flags.contains(IntSourceRegM::PTH_Y)
+    }
+
+    /// Z-axis value exceeds the threshold on the positive side.
+    #[inline]
+    pub const fn z_positive(&self) -> bool {
+        self.flags.contains(IntSourceRegM::PTH_Z)
+    }
+
+    /// X-axis value exceeds the threshold on the negative side.
+    #[inline]
+    pub const fn x_negative(&self) -> bool {
+        self.flags.contains(IntSourceRegM::NTH_X)
+    }
+
+    /// Y-axis value exceeds the threshold on the negative side.
+    #[inline]
+    pub const fn y_negative(&self) -> bool {
+        self.flags.contains(IntSourceRegM::NTH_Y)
+    }
+
+    /// Z-axis value exceeds the threshold on the negative side.
+    #[inline]
+    pub const fn z_negative(&self) -> bool {
+        self.flags.contains(IntSourceRegM::NTH_Z)
+    }
+
+    /// Internal measurement range overflow.
+    #[inline]
+    pub const fn measurement_range_overflow(&self) -> bool {
+        self.flags.contains(IntSourceRegM::MROI)
+    }
+
+    /// Interrupt event occurred.
+    #[inline]
+    pub const fn interrupt_occurred(&self) -> bool {
+        self.flags.contains(IntSourceRegM::INT)
+    }
+}
+
+/// Error returned when converting an invalid value into a setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidValueError {
+    setting: &'static str,
+    value: u16,
+}
+
+impl InvalidValueError {
+    const fn new(setting: &'static str, value: u16) -> Self {
+        Self { setting, value }
+    }
+
+    /// The value which could not be converted.
+    pub const fn value(&self) -> u16 {
+        self.value
+    }
+}
+
+impl fmt::Display for InvalidValueError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid {}: {}", self.setting, self.value)
+    }
+}
+
+#[cfg(feature = "core-error")]
+impl core::error::Error for InvalidValueError {}
+
+impl TryFrom<u16> for AccelOutputDataRate {
+    type Error = InvalidValueError;
+
+    /// Convert a frequency in Hertz.
+    fn try_from(hz: u16) -> Result<Self, Self::Error> {
+        Self::from_hertz(hz)
+            .ok_or_else(|| InvalidValueError::new("accelerometer output data rate", hz))
+    }
+}
+
+impl From<AccelOutputDataRate> for u16 {
+    /// Convert into a frequency in Hertz.
+    fn from(odr: AccelOutputDataRate) -> Self {
+        odr.to_hertz()
+    }
+}
+
+impl PartialOrd for AccelOutputDataRate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for AccelOutputDataRate {
+    /// Compare by frequency.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.to_hertz().cmp(&other.to_hertz())
+    }
+}
+
+impl TryFrom<u16> for MagOutputDataRate {
+    type Error = InvalidValueError;
+
+    /// Convert a frequency in Hertz.
+    fn try_from(hz: u16) -> Result<Self, Self::Error> {
+        Self::from_hertz(hz)
+            .ok_or_else(|| InvalidValueError::new("magnetometer output data rate", hz))
+    }
+}
+
+impl From<MagOutputDataRate> for u16 {
+    /// Convert into a frequency in Hertz.
+    fn from(odr: MagOutputDataRate) -> Self {
+        odr.to_hertz()
+    }
+}
+
+impl PartialOrd for MagOutputDataRate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MagOutputDataRate {
+    /// Compare by frequency.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.to_hertz().cmp(&other.to_hertz())
+    }
+}
+
+impl TryFrom<u16> for AccelScale {
+    type Error = InvalidValueError;
+
+    /// Convert a full scale in *g*.
+    fn try_from(g: u16) -> Result<Self, Self::Error> {
+        Ok(match g {
+            2 => Self::G2,
+            4 => Self::G4,
+            8 => Self::G8,
+            16 => Self::G16,
+            _ => return Err(InvalidValueError::new("accelerometer scale", g)),
+        })
+    }
+}
+
+impl From<AccelScale> for u16 {
+    /// Convert into a full scale in *g*.
+    fn from(scale: AccelScale) -> Self {
+        scale as u16
+    }
+}
+
+impl TryFrom<u16> for AccelMode {
+    type Error = InvalidValueError;
+
+    /// Convert a resolution in bits, where 0 corresponds to power-down mode.
+    fn try_from(bits: u16) -> Result<Self, Self::Error> {
+        Ok(match bits {
+            0 => Self::PowerDown,
+            8 => Self::LowPower,
+            10 => Self::Normal,
+            12 => Self::HighResolution,
+            _ => {
+                return Err(InvalidValueError::new(
+                    "accelerometer mode resolution",
+                    bits,
+                ))
+            }
+        })
+    }
+}
+
+impl From<AccelMode> for u16 {
+    /// Convert into a resolution in bits, where 0 corresponds to power-down mode.
+    fn from(mode: AccelMode) -> Self {
+        match mode {
+            AccelMode::PowerDown => 0,
+            AccelMode::LowPower => 8,
+            AccelMode::Normal => 10,
+            AccelMode::HighResolution => 12,
+        }
+    }
+}
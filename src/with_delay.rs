@@ -0,0 +1,155 @@
+use embedded_hal::blocking::delay::DelayUs;
+
+use crate::{
+    interface::{ReadData, WriteData},
+    mode, AccelMode, AccelOutputDataRate, Acceleration, Error, InterruptLine, Lsm303agr, MagMode,
+    MagOutputDataRate, MagneticField, MilliG,
+};
+
+impl<DI, CommE, PinE, MODE> Lsm303agr<DI, MODE>
+where
+    DI: ReadData<Error = Error<CommE, PinE>> + WriteData<Error = Error<CommE, PinE>>,
+{
+    /// Use the given `delay` for all methods which need to wait for the device.
+    ///
+    /// This returns a [`WithDelay`] wrapper providing the methods which otherwise
+    /// take a delay argument, without this argument. The delay can be taken back
+    /// using [`WithDelay::release`].
+    pub fn with_delay<D: DelayUs<u32>>(&mut self, delay: D) -> WithDelay<'_, DI, MODE, D> {
+        WithDelay { dev: self, delay }
+    }
+}
+
+/// Device driver using a stored delay.
+///
+/// All methods which take a delay argument are available without it, except for
+/// [`Lsm303agr::preset_high_performance`], which consumes the device and therefore
+/// cannot be called through this borrowing wrapper.
+///
+/// See [`Lsm303agr::with_delay`].
+#[derive(Debug)]
+pub struct WithDelay<'a, DI, MODE, D> {
+    dev: &'a mut Lsm303agr<DI, MODE>,
+    delay: D,
+}
+
+impl<'a, DI, CommE, PinE, MODE, D> WithDelay<'a, DI, MODE, D>
+where
+    DI: ReadData<Error = Error<CommE, PinE>> + WriteData<Error = Error<CommE, PinE>>,
+    D: DelayUs<u32>,
+{
+    /// Set accelerometer output data rate.
+    ///
+    /// See [`Lsm303agr::set_accel_odr`].
+    pub fn set_accel_odr(&mut self, odr: AccelOutputDataRate) -> Result<(), Error<CommE, PinE>> {
+        self.dev.set_accel_odr(&mut self.delay, odr)
+    }
+
+    /// Set accelerometer power/resolution mode.
+    ///
+    /// See [`Lsm303agr::set_accel_mode`].
+    pub fn set_accel_mode(&mut self, mode: AccelMode) -> Result<(), Error<CommE, PinE>> {
+        self.dev.set_accel_mode(&mut self.delay, mode)
+    }
+
+    /// Set magnetometer output data rate.
+    ///
+    /// See [`Lsm303agr::set_mag_odr`].
+    pub fn set_mag_odr(&mut self, odr: MagOutputDataRate) -> Result<(), Error<CommE, PinE>> {
+        self.dev.set_mag_odr(&mut self.delay, odr)
+    }
+
+    /// Set magnetometer power mode.
+    ///
+    /// See [`Lsm303agr::set_mag_mode`].
+    pub fn set_mag_mode(&mut self, mode: MagMode) -> Result<(), Error<CommE, PinE>> {
+        self.dev.set_mag_mode(&mut self.delay, mode)
+    }
+
+    /// Enable or disable magnetometer low-power mode.
+    ///
+    /// See [`Lsm303agr::mag_set_low_power`].
+    pub fn mag_set_low_power(&mut self, enabled: bool) -> Result<(), Error<CommE, PinE>> {
+        self.dev.mag_set_low_power(&mut self.delay, enabled)
+    }
+
+    /// Configure the accelerometer FIFO to capture samples around a trigger event.
+    ///
+    /// See [`Lsm303agr::acc_configure_trigger_capture`].
+    pub fn acc_configure_trigger_capture(
+        &mut self,
+        pre_samples: u8,
+        trigger: InterruptLine,
+    ) -> Result<(), Error<CommE, PinE>> {
+        self.dev
+            .acc_configure_trigger_capture(pre_samples, trigger, &mut self.delay)
+    }
+
+    /// Use the current acceleration as the zero reference.
+    ///
+    /// See [`Lsm303agr::acc_autozero`].
+    pub fn acc_autozero(&mut self) -> Result<(), Error<CommE, PinE>> {
+        self.dev.acc_autozero(&mut self.delay)
+    }
+
+    /// Configure wake on motion.
+    ///
+    /// See [`Lsm303agr::acc_configure_wake_on_motion`].
+    pub fn acc_configure_wake_on_motion(
+        &mut self,
+        threshold: MilliG,
+        duration: u8,
+    ) -> Result<(Option<AccelOutputDataRate>, AccelMode), Error<CommE, PinE>> {
+        self.dev
+            .acc_configure_wake_on_motion(&mut self.delay, threshold, duration)
+    }
+
+    /// Read acceleration samples into a buffer.
+    ///
+    /// See [`Lsm303agr::acceleration_batch`].
+    pub fn acceleration_batch(
+        &mut self,
+        out: &mut [Option<Acceleration>],
+    ) -> Result<usize, Error<CommE, PinE>> {
+        self.dev.acceleration_batch(out, &mut self.delay)
+    }
+
+    /// Recover the magnetometer after exposure to a strong magnetic field.
+    ///
+    /// See [`Lsm303agr::mag_recover_from_saturation`].
+    pub fn mag_recover_from_saturation(&mut self) -> Result<(), Error<CommE, PinE>> {
+        self.dev.mag_recover_from_saturation(&mut self.delay)
+    }
+
+    /// Release the stored delay.
+    pub fn release(self) -> D {
+        self.delay
+    }
+}
+
+impl<'a, DI, CommE, PinE, D> WithDelay<'a, DI, mode::MagOneShot, D>
+where
+    DI: ReadData<Error = Error<CommE, PinE>> + WriteData<Error = Error<CommE, PinE>>,
+    D: DelayUs<u32>,
+{
+    /// Reset the device to its power-on defaults.
+    ///
+    /// See [`Lsm303agr::reset`].
+    pub fn reset(&mut self) -> Result<(), Error<CommE, PinE>> {
+        self.dev.reset(&mut self.delay)
+    }
+
+    /// Take a single magnetic field measurement.
+    ///
+    /// See [`Lsm303agr::magnetic_field_blocking`].
+    pub fn magnetic_field_blocking(&mut self) -> Result<MagneticField, Error<CommE, PinE>> {
+        self.dev.magnetic_field_blocking(&mut self.delay)
+    }
+
+    /// Configure the device for low power consumption.
+    ///
+    /// See [`Lsm303agr::preset_low_power`].
+    pub fn preset_low_power(&mut self) -> Result<(), Error<CommE, PinE>> {
+        self.dev.preset_low_power(&mut self.delay)
+    }
+}
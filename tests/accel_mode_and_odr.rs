@@ -1,9 +1,14 @@
 mod common;
 use crate::common::{
-    destroy_i2c, new_i2c, BitFlags as BF, Register, ACCEL_ADDR, DEFAULT_CTRL_REG1_A,
+    destroy_i2c, new_i2c, BitFlags as BF, RecordingDelay, Register, ACCEL_ADDR,
+    DEFAULT_CTRL_REG1_A, HZ50,
 };
 use embedded_hal_mock::{delay::MockNoop as Delay, i2c::Transaction as I2cTrans};
-use lsm303agr::{AccelMode as Mode, AccelOutputDataRate as ODR, FifoMode, Interrupt};
+use lsm303agr::{
+    AccelClickConfig, AccelHighPassCutoff, AccelHighPassMode, AccelInterruptConfig,
+    AccelMode as Mode, AccelOutputDataRate as ODR, Error, FifoMode, Interrupt, InterruptLine,
+    InterruptSource, MilliG,
+};
 
 macro_rules! normal_pwr {
     ($name:ident, $hz:ident, $value:expr) => {
@@ -57,6 +62,88 @@ fn normal_pwr_enable_lp_khz_5_376() {
     destroy_i2c(sensor);
 }
 
+#[test]
+fn can_get_odr() {
+    let mut sensor = new_i2c(&[I2cTrans::write(
+        ACCEL_ADDR,
+        vec![Register::CTRL_REG1_A, 4 << 4 | DEFAULT_CTRL_REG1_A],
+    )]);
+    assert_eq!(sensor.get_accel_odr(), None);
+    assert_eq!(sensor.get_accel_odr_hz(), None);
+    sensor.set_accel_odr(&mut Delay, ODR::Hz50).unwrap();
+    assert_eq!(sensor.get_accel_odr(), Some(ODR::Hz50));
+    assert_eq!(sensor.get_accel_odr_hz(), Some(50));
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_set_odr_without_waiting() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, 4 << 4 | DEFAULT_CTRL_REG1_A],
+        ),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, 5 << 4 | DEFAULT_CTRL_REG1_A],
+        ),
+    ]);
+    // Turning on from power-down mode into normal mode.
+    assert_eq!(sensor.set_accel_odr_no_wait(ODR::Hz50).unwrap(), 1600);
+    // No mode change.
+    assert_eq!(sensor.set_accel_odr_no_wait(ODR::Hz100).unwrap(), 0);
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_set_mode_without_waiting() {
+    let odr_50hz = 4 << 4 | DEFAULT_CTRL_REG1_A;
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG1_A, odr_50hz]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG1_A, odr_50hz]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, BF::HR]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG1_A, odr_50hz]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A]),
+    ]);
+    sensor.set_accel_odr(&mut Delay, ODR::Hz50).unwrap();
+    // 7 samples at 50 Hz.
+    assert_eq!(
+        sensor.set_accel_mode_no_wait(Mode::HighResolution).unwrap(),
+        140_000
+    );
+    // 1 sample at 50 Hz.
+    assert_eq!(sensor.set_accel_mode_no_wait(Mode::Normal).unwrap(), 20_000);
+    // Nothing to wait for when powering down.
+    assert_eq!(sensor.set_accel_mode_no_wait(Mode::PowerDown).unwrap(), 0);
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn mode_change_time_is_one_sample_period_in_us() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, 1 << 4 | DEFAULT_CTRL_REG1_A],
+        ),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![
+                Register::CTRL_REG1_A,
+                1 << 4 | BF::LP_EN | DEFAULT_CTRL_REG1_A,
+            ],
+        ),
+    ]);
+    sensor.set_accel_odr(&mut Delay, ODR::Hz1).unwrap();
+    // 1 sample at 1 Hz.
+    assert_eq!(
+        sensor.set_accel_mode_no_wait(Mode::LowPower).unwrap(),
+        1_000_000
+    );
+    destroy_i2c(sensor);
+}
+
 #[test]
 fn from_high_resolution_to_low_power_only_odr() {
     let mut sensor = new_i2c(&[
@@ -83,8 +170,8 @@ fn from_high_resolution_to_low_power_only_odr() {
 #[test]
 fn from_normal_to_low_power_only_odr() {
     let mut sensor = new_i2c(&[
-        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A]),
         I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A]),
         I2cTrans::write(
             ACCEL_ADDR,
             vec![
@@ -126,9 +213,33 @@ fn can_power_down() {
 #[test]
 fn can_set_mode_normal() {
     let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0]),
         I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A]),
+    ]);
+    sensor.set_accel_mode(&mut Delay, Mode::Normal).unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn mode_transitions_write_each_register_once() {
+    let mut sensor = new_i2c(&[
+        // Normal -> high-resolution
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, BF::HR]),
+        // High-resolution -> low-power
         I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, BF::LP_EN | DEFAULT_CTRL_REG1_A],
+        ),
+        // Low-power -> normal
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A]),
     ]);
+    sensor
+        .set_accel_mode(&mut Delay, Mode::HighResolution)
+        .unwrap();
+    sensor.set_accel_mode(&mut Delay, Mode::LowPower).unwrap();
     sensor.set_accel_mode(&mut Delay, Mode::Normal).unwrap();
     destroy_i2c(sensor);
 }
@@ -180,6 +291,59 @@ fn can_power_down_after_odr3() {
     destroy_i2c(sensor);
 }
 
+#[test]
+fn can_set_high_pass_filter() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG2_A, 0b10111000]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG2_A, 0b01010000]),
+    ]);
+    sensor
+        .set_accel_high_pass_filter(
+            AccelHighPassMode::Normal,
+            AccelHighPassCutoff::OdrDiv400,
+            true,
+        )
+        .unwrap();
+    sensor
+        .set_accel_high_pass_filter(
+            AccelHighPassMode::Reference,
+            AccelHighPassCutoff::OdrDiv100,
+            false,
+        )
+        .unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_autozero() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG2_A, 0b01010000]),
+        // Normal mode with reset, cut-off kept, filtered data.
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG2_A, 0b00011000]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![0x26], vec![0]),
+    ]);
+    sensor
+        .acc_autozero(&mut Delay)
+        .expect_err("should have returned error");
+    sensor.set_accel_odr(&mut Delay, ODR::Hz50).unwrap();
+    sensor
+        .set_accel_high_pass_filter(
+            AccelHighPassMode::Reference,
+            AccelHighPassCutoff::OdrDiv100,
+            false,
+        )
+        .unwrap();
+    let mut delay = RecordingDelay::default();
+    sensor.acc_autozero(&mut delay).unwrap();
+    // 1 sample at 50 Hz.
+    assert_eq!(delay.delays_us, [20_000]);
+    destroy_i2c(sensor);
+}
+
 #[test]
 fn can_enable_disable_interrupts() {
     let mut sensor = new_i2c(&[
@@ -195,6 +359,106 @@ fn can_enable_disable_interrupts() {
     destroy_i2c(sensor);
 }
 
+#[test]
+fn can_route_interrupt_to_int2() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG6_A, 0b0100_0000]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG3_A, 0b0100_0000]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG6_A, 0]),
+    ]);
+    sensor
+        .acc_enable_interrupt((InterruptLine::Int2, InterruptSource::Aoi1))
+        .unwrap();
+    sensor
+        .acc_enable_interrupt((InterruptLine::Int1, InterruptSource::Aoi1))
+        .unwrap();
+    sensor
+        .acc_disable_interrupt((InterruptLine::Int2, InterruptSource::Aoi1))
+        .unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn cannot_route_unsupported_interrupt() {
+    let mut sensor = new_i2c(&[]);
+    assert!(matches!(
+        sensor.acc_enable_interrupt((InterruptLine::Int2, InterruptSource::DataReady1)),
+        Err(Error::InvalidInputData)
+    ));
+    assert!(matches!(
+        sensor.acc_enable_interrupt((InterruptLine::Int1, InterruptSource::Activity)),
+        Err(Error::InvalidInputData)
+    ));
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_enable_fifo_interrupts() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG3_A, 0b110]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG3_A, 0b010]),
+    ]);
+    sensor
+        .acc_enable_fifo_interrupts(true, true, InterruptLine::Int1)
+        .unwrap();
+    sensor
+        .acc_enable_fifo_interrupts(false, true, InterruptLine::Int1)
+        .unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn cannot_enable_fifo_interrupts_on_int2() {
+    let mut sensor = new_i2c(&[]);
+    sensor
+        .acc_enable_fifo_interrupts(true, true, InterruptLine::Int2)
+        .expect_err("should have returned error");
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_enable_data_ready_interrupt() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG6_A, BF::H_LACTIVE]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG3_A, BF::I1_DRDY1]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG6_A, 0]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG3_A, BF::I1_DRDY1]),
+    ]);
+    sensor
+        .acc_enable_data_ready_interrupt(InterruptLine::Int1, true)
+        .unwrap();
+    sensor
+        .acc_enable_data_ready_interrupt(InterruptLine::Int1, false)
+        .unwrap();
+    sensor
+        .acc_enable_data_ready_interrupt(InterruptLine::Int2, false)
+        .expect_err("should have returned error");
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn is_not_ready_when_powered_down() {
+    let mut sensor = new_i2c(&[]);
+    assert!(!sensor.acc_is_ready().unwrap());
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_check_if_ready() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | 5 << 4],
+        ),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::STATUS_REG_A], vec![0]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::STATUS_REG_A], vec![BF::XYZDR]),
+    ]);
+    sensor.set_accel_odr(&mut Delay, ODR::Hz100).unwrap();
+    assert!(!sensor.acc_is_ready().unwrap());
+    assert!(sensor.acc_is_ready().unwrap());
+    destroy_i2c(sensor);
+}
+
 #[test]
 fn can_set_fifo_mode() {
     let mut sensor = new_i2c(&[
@@ -216,3 +480,269 @@ fn can_set_fifo_mode() {
     sensor.acc_set_fifo_mode(FifoMode::Bypass, 0).unwrap();
     destroy_i2c(sensor);
 }
+
+#[test]
+fn can_set_fifo_watermark() {
+    let mut sensor = new_i2c(&[
+        // Stream mode, 0
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG5_A, 0b01000000]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::FIFO_CTRL_REG_A, 0b10000000]),
+        // Stream mode, 31
+        I2cTrans::write(ACCEL_ADDR, vec![Register::FIFO_CTRL_REG_A, 0b10011111]),
+        // Stream mode, 4
+        I2cTrans::write(ACCEL_ADDR, vec![Register::FIFO_CTRL_REG_A, 0b10000100]),
+    ]);
+    sensor.acc_set_fifo_mode(FifoMode::Stream, 0).unwrap();
+    sensor.set_fifo_watermark(31).unwrap();
+    sensor.set_fifo_watermark(4).unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn cannot_set_fifo_watermark_out_of_range() {
+    let mut sensor = new_i2c(&[]);
+    sensor
+        .set_fifo_watermark(32)
+        .expect_err("should have returned error");
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_configure_trigger_capture() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG5_A, 0b01000000]),
+        // Bypass mode, trigger on INT2, watermark of 10 samples.
+        I2cTrans::write(ACCEL_ADDR, vec![Register::FIFO_CTRL_REG_A, 0b00101010]),
+        // Stream-to-FIFO mode.
+        I2cTrans::write(ACCEL_ADDR, vec![Register::FIFO_CTRL_REG_A, 0b11101010]),
+    ]);
+    sensor.set_accel_odr(&mut Delay, ODR::Hz50).unwrap();
+    let mut delay = RecordingDelay::default();
+    sensor
+        .acc_configure_trigger_capture(10, InterruptLine::Int2, &mut delay)
+        .unwrap();
+    // 10 samples at 50 Hz.
+    assert_eq!(delay.delays_us, [200_000]);
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn cannot_configure_trigger_capture_with_invalid_input() {
+    let mut sensor = new_i2c(&[I2cTrans::write(
+        ACCEL_ADDR,
+        vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
+    )]);
+    // Powered down.
+    sensor
+        .acc_configure_trigger_capture(10, InterruptLine::Int1, &mut Delay)
+        .expect_err("should have returned error");
+    sensor.set_accel_odr(&mut Delay, ODR::Hz50).unwrap();
+    sensor
+        .acc_configure_trigger_capture(32, InterruptLine::Int1, &mut Delay)
+        .expect_err("should have returned error");
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_set_fifo_trigger() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::FIFO_CTRL_REG_A, 0b00100000]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::FIFO_CTRL_REG_A, 0b00000000]),
+    ]);
+    sensor.acc_set_fifo_trigger(InterruptLine::Int2).unwrap();
+    sensor.acc_set_fifo_trigger(InterruptLine::Int1).unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_configure_click() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | 5 << 4],
+        ),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CLICK_THS_A, 0x80 | 32]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::TIME_LIMIT_A, 5]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::TIME_LATENCY_A, 10]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::TIME_WINDOW_A, 30]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CLICK_CFG_A, 0b00110000]),
+    ]);
+    sensor.set_accel_odr(&mut Delay, ODR::Hz100).unwrap();
+    sensor
+        .acc_configure_click(AccelClickConfig {
+            z_single: true,
+            z_double: true,
+            latched: true,
+            threshold: MilliG(500),
+            time_limit_us: 50_000,
+            time_latency_us: 100_000,
+            time_window_us: 300_000,
+            ..Default::default()
+        })
+        .unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn cannot_configure_click_out_of_range() {
+    let mut sensor = new_i2c(&[I2cTrans::write(
+        ACCEL_ADDR,
+        vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | 5 << 4],
+    )]);
+    sensor
+        .acc_configure_click(AccelClickConfig::default())
+        .expect_err("should have returned error");
+    sensor.set_accel_odr(&mut Delay, ODR::Hz100).unwrap();
+    sensor
+        .acc_configure_click(AccelClickConfig {
+            threshold: MilliG(2000),
+            ..Default::default()
+        })
+        .expect_err("should have returned error");
+    sensor
+        .acc_configure_click(AccelClickConfig {
+            time_latency_us: 2_000_000,
+            time_window_us: 1_000_000,
+            ..Default::default()
+        })
+        .expect_err("should have returned error");
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_configure_interrupt_generator() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::INT1_THS_A, 0x7F]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::INT1_DURATION_A, 0x05]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::INT1_CFG_A, 0b11101010]),
+    ]);
+    sensor
+        .acc_configure_interrupt_generator(AccelInterruptConfig {
+            and_combination: true,
+            six_direction: true,
+            z_high: true,
+            y_high: true,
+            x_high: true,
+            threshold: 0x7F,
+            duration: 0x05,
+            ..Default::default()
+        })
+        .unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn interrupt_generator_threshold_out_of_range() {
+    let mut sensor = new_i2c(&[]);
+    sensor
+        .acc_configure_interrupt_generator(AccelInterruptConfig {
+            threshold: 0x80,
+            ..Default::default()
+        })
+        .expect_err("should have returned error");
+    sensor
+        .acc_configure_interrupt_generator(AccelInterruptConfig {
+            duration: 0x80,
+            ..Default::default()
+        })
+        .expect_err("should have returned error");
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_configure_wake_on_motion() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, 4 << 4 | DEFAULT_CTRL_REG1_A],
+        ),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, 2 << 4 | DEFAULT_CTRL_REG1_A],
+        ),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![
+                Register::CTRL_REG1_A,
+                2 << 4 | BF::LP_EN | DEFAULT_CTRL_REG1_A,
+            ],
+        ),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::ACT_THS_A, 15]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::ACT_DUR_A, 2]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG6_A, BF::P2_ACT]),
+    ]);
+    sensor.set_accel_odr(&mut Delay, ODR::Hz50).unwrap();
+    let previous = sensor
+        .acc_configure_wake_on_motion(&mut Delay, MilliG(250), 2)
+        .unwrap();
+    assert_eq!(previous, (Some(ODR::Hz50), Mode::Normal));
+    assert_eq!(sensor.get_accel_mode(), Mode::LowPower);
+    assert_eq!(sensor.get_accel_odr(), Some(ODR::Hz10));
+    sensor
+        .acc_configure_wake_on_motion(&mut Delay, MilliG(2100), 2)
+        .expect_err("should have returned error");
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_configure_wake_on_motion_when_powered_down() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, 2 << 4 | DEFAULT_CTRL_REG1_A],
+        ),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![
+                Register::CTRL_REG1_A,
+                2 << 4 | BF::LP_EN | DEFAULT_CTRL_REG1_A,
+            ],
+        ),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::ACT_THS_A, 15]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::ACT_DUR_A, 2]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG6_A, BF::P2_ACT]),
+    ]);
+    let previous = sensor
+        .acc_configure_wake_on_motion(&mut Delay, MilliG(250), 2)
+        .unwrap();
+    assert_eq!(previous, (None, Mode::PowerDown));
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_get_interrupt_source() {
+    let mut sensor = new_i2c(&[I2cTrans::write_read(
+        ACCEL_ADDR,
+        vec![Register::INT1_SRC_A],
+        vec![0b01010010],
+    )]);
+    let source = sensor.acc_interrupt_source().unwrap();
+    assert!(source.active());
+    assert!(!source.z_high());
+    assert!(source.z_low());
+    assert!(!source.y_high());
+    assert!(!source.y_low());
+    assert!(source.x_high());
+    assert!(!source.x_low());
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_get_both_interrupt_sources() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::INT1_SRC_A], vec![0]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::INT2_SRC_A], vec![0b01100000]),
+    ]);
+    let (int1, int2) = sensor.acc_interrupt_sources().unwrap();
+    assert!(!int1.active());
+    assert!(int2.active());
+    assert!(int2.z_high());
+    assert!(!int2.z_low());
+    destroy_i2c(sensor);
+}
@@ -24,16 +24,37 @@ impl Register {
     pub const WHO_AM_I_A: u8 = 0x0F;
     pub const TEMP_CFG_REG_A: u8 = 0x1F;
     pub const CTRL_REG1_A: u8 = 0x20;
+    pub const CTRL_REG2_A: u8 = 0x21;
     pub const CTRL_REG3_A: u8 = 0x22;
     pub const CTRL_REG4_A: u8 = 0x23;
     pub const CTRL_REG5_A: u8 = 0x24;
+    pub const CTRL_REG6_A: u8 = 0x25;
+    pub const REFERENCE_A: u8 = 0x26;
     pub const FIFO_CTRL_REG_A: u8 = 0x2E;
+    pub const FIFO_SRC_REG_A: u8 = 0x2F;
     pub const STATUS_REG_A: u8 = 0x27;
     pub const OUT_X_L_A: u8 = 0x28;
+    pub const INT1_CFG_A: u8 = 0x30;
+    pub const INT1_SRC_A: u8 = 0x31;
+    pub const INT1_THS_A: u8 = 0x32;
+    pub const INT1_DURATION_A: u8 = 0x33;
+    pub const INT2_CFG_A: u8 = 0x34;
+    pub const INT2_SRC_A: u8 = 0x35;
+    pub const INT2_THS_A: u8 = 0x36;
+    pub const INT2_DURATION_A: u8 = 0x37;
+    pub const CLICK_CFG_A: u8 = 0x38;
+    pub const CLICK_THS_A: u8 = 0x3A;
+    pub const TIME_LIMIT_A: u8 = 0x3B;
+    pub const TIME_LATENCY_A: u8 = 0x3C;
+    pub const TIME_WINDOW_A: u8 = 0x3D;
+    pub const ACT_THS_A: u8 = 0x3E;
+    pub const ACT_DUR_A: u8 = 0x3F;
     pub const WHO_AM_I_M: u8 = 0x4F;
     pub const CFG_REG_A_M: u8 = 0x60;
     pub const CFG_REG_B_M: u8 = 0x61;
     pub const CFG_REG_C_M: u8 = 0x62;
+    pub const INT_CRTL_REG_M: u8 = 0x63;
+    pub const INT_SOURCE_REG_M: u8 = 0x64;
     pub const STATUS_REG_M: u8 = 0x67;
     pub const OUTX_L_REG_M: u8 = 0x68;
 }
@@ -50,9 +71,22 @@ impl BitFlags {
     pub const LP_EN: u8 = 1 << 3;
 
     pub const ACCEL_BDU: u8 = 1 << 7;
+    pub const ACCEL_BLE: u8 = 1 << 6;
     pub const HR: u8 = 1 << 3;
+    pub const BOOT: u8 = 1 << 7;
+    pub const P2_ACT: u8 = 1 << 3;
+    pub const H_LACTIVE: u8 = 1 << 1;
+    pub const I1_DRDY1: u8 = 1 << 4;
+    pub const SPI_ENABLE: u8 = 1;
+    pub const FIFO_WTM: u8 = 1 << 7;
+    pub const FIFO_OVRN: u8 = 1 << 6;
+    pub const FIFO_EMPTY: u8 = 1 << 5;
 
     pub const MAG_BDU: u8 = 1 << 4;
+    pub const MAG_BLE: u8 = 1 << 3;
+    pub const SOFT_RST: u8 = 1 << 5;
+    pub const REBOOT: u8 = 1 << 6;
+    pub const I2C_DIS: u8 = 1 << 5;
 
     pub const MAG_OFF_CANC: u8 = 1 << 1;
     pub const MAG_OFF_CANC_ONE_SHOT: u8 = 1 << 4;
@@ -73,6 +107,61 @@ impl BitFlags {
     pub const TEMP_EN1: u8 = 1 << 7;
 }
 
+/// Accelerometer registers and default values written by `reset()` after rebooting.
+#[allow(unused)]
+pub const ACCEL_RESET_DEFAULTS: [(u8, u8); 22] = [
+    (Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A),
+    (Register::CTRL_REG2_A, 0),
+    (Register::CTRL_REG3_A, 0),
+    (Register::CTRL_REG4_A, 0),
+    (Register::CTRL_REG5_A, 0),
+    (Register::CTRL_REG6_A, 0),
+    (Register::REFERENCE_A, 0),
+    (Register::TEMP_CFG_REG_A, 0),
+    (Register::FIFO_CTRL_REG_A, 0),
+    (Register::INT1_CFG_A, 0),
+    (Register::INT1_THS_A, 0),
+    (Register::INT1_DURATION_A, 0),
+    (Register::INT2_CFG_A, 0),
+    (Register::INT2_THS_A, 0),
+    (Register::INT2_DURATION_A, 0),
+    (Register::CLICK_CFG_A, 0),
+    (Register::CLICK_THS_A, 0),
+    (Register::TIME_LIMIT_A, 0),
+    (Register::TIME_LATENCY_A, 0),
+    (Register::TIME_WINDOW_A, 0),
+    (Register::ACT_THS_A, 0),
+    (Register::ACT_DUR_A, 0),
+];
+
+/// Transactions of a `reset()` via I2C without write verification.
+#[allow(unused)]
+pub fn reset_i2c_transactions() -> Vec<I2cTrans> {
+    let mut transactions = vec![
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG5_A, BitFlags::BOOT]),
+        I2cTrans::write(
+            MAG_ADDR,
+            vec![
+                Register::CFG_REG_A_M,
+                BitFlags::SOFT_RST | DEFAULT_CFG_REG_A_M,
+            ],
+        ),
+        I2cTrans::write(
+            MAG_ADDR,
+            vec![
+                Register::CFG_REG_A_M,
+                BitFlags::REBOOT | DEFAULT_CFG_REG_A_M,
+            ],
+        ),
+    ];
+    transactions.extend(
+        ACCEL_RESET_DEFAULTS
+            .iter()
+            .map(|&(reg, value)| I2cTrans::write(ACCEL_ADDR, vec![reg, value])),
+    );
+    transactions
+}
+
 #[allow(unused)]
 pub fn default_cs() -> PinMock {
     default_cs_n(1)
@@ -156,3 +245,16 @@ macro_rules! assert_eq_xyz {
         assert_eq!($data.$z_unit(), z_unit);
     }};
 }
+
+/// Delay recording the requested delays instead of waiting.
+#[allow(unused)]
+#[derive(Debug, Default)]
+pub struct RecordingDelay {
+    pub delays_us: Vec<u32>,
+}
+
+impl embedded_hal::blocking::delay::DelayUs<u32> for RecordingDelay {
+    fn delay_us(&mut self, us: u32) {
+        self.delays_us.push(us);
+    }
+}
@@ -0,0 +1,312 @@
+use core::convert::TryFrom;
+use lsm303agr::{
+    accel_self_test_limits_mg, accel_self_test_passed, AccelMode, AccelOutputDataRate, AccelScale,
+    Acceleration, MagOutputDataRate, MagneticField,
+};
+
+macro_rules! assert_round_trip {
+    ($ty:ident: $($variant:ident => $value:expr),* ; invalid: $($invalid:expr),*) => {{
+        $(
+            assert_eq!($ty::try_from($value), Ok($ty::$variant));
+            assert_eq!(u16::from($ty::$variant), $value);
+        )*
+        $(
+            let error = $ty::try_from($invalid).unwrap_err();
+            assert_eq!(error.value(), $invalid);
+        )*
+    }};
+}
+
+#[test]
+fn accel_odr_round_trip() {
+    assert_round_trip!(AccelOutputDataRate:
+        Hz1 => 1,
+        Hz10 => 10,
+        Hz25 => 25,
+        Hz50 => 50,
+        Hz100 => 100,
+        Hz200 => 200,
+        Hz400 => 400,
+        Khz1_344 => 1344,
+        Khz1_620LowPower => 1620,
+        Khz5_376LowPower => 5376;
+        invalid: 0, 20, 3333
+    );
+}
+
+#[test]
+fn mag_odr_round_trip() {
+    assert_round_trip!(MagOutputDataRate:
+        Hz10 => 10,
+        Hz20 => 20,
+        Hz50 => 50,
+        Hz100 => 100;
+        invalid: 0, 1, 33
+    );
+}
+
+#[test]
+fn odr_tables_list_all_rates() {
+    let accel_hz: Vec<u16> = AccelOutputDataRate::ALL
+        .iter()
+        .map(|odr| odr.to_hertz())
+        .collect();
+    assert_eq!(accel_hz, [1, 10, 25, 50, 100, 200, 400, 1344, 1620, 5376]);
+    for &odr in AccelOutputDataRate::ALL {
+        assert_eq!(AccelOutputDataRate::from_hertz(odr.to_hertz()), Some(odr));
+    }
+
+    let mag_hz: Vec<u16> = MagOutputDataRate::ALL
+        .iter()
+        .map(|odr| odr.to_hertz())
+        .collect();
+    assert_eq!(mag_hz, [10, 20, 50, 100]);
+    for &odr in MagOutputDataRate::ALL {
+        assert_eq!(MagOutputDataRate::from_hertz(odr.to_hertz()), Some(odr));
+    }
+}
+
+#[test]
+fn odrs_are_ordered_by_frequency() {
+    for (&a, &b) in AccelOutputDataRate::ALL
+        .iter()
+        .zip(AccelOutputDataRate::ALL.iter().skip(1))
+    {
+        assert!(a < b);
+        assert_eq!(a.cmp(&b), a.to_hertz().cmp(&b.to_hertz()));
+    }
+    assert!(AccelOutputDataRate::Khz1_344 < AccelOutputDataRate::Khz1_620LowPower);
+
+    for (&a, &b) in MagOutputDataRate::ALL
+        .iter()
+        .zip(MagOutputDataRate::ALL.iter().skip(1))
+    {
+        assert!(a < b);
+        assert_eq!(a.cmp(&b), a.to_hertz().cmp(&b.to_hertz()));
+    }
+    assert_eq!(
+        MagOutputDataRate::ALL.iter().max(),
+        Some(&MagOutputDataRate::Hz100)
+    );
+}
+
+#[test]
+fn accel_scale_round_trip() {
+    assert_round_trip!(AccelScale:
+        G2 => 2,
+        G4 => 4,
+        G8 => 8,
+        G16 => 16;
+        invalid: 0, 1, 3, 32
+    );
+
+    for scale in [
+        AccelScale::G2,
+        AccelScale::G4,
+        AccelScale::G8,
+        AccelScale::G16,
+    ] {
+        assert_eq!(AccelScale::from_g(scale.to_g()), Some(scale));
+    }
+    assert_eq!(AccelScale::from_g(3), None);
+    assert_eq!(AccelScale::from_g(32), None);
+}
+
+#[test]
+fn accel_mode_round_trip() {
+    assert_round_trip!(AccelMode:
+        PowerDown => 0,
+        LowPower => 8,
+        Normal => 10,
+        HighResolution => 12;
+        invalid: 1, 16
+    );
+}
+
+#[test]
+fn invalid_value_error_is_descriptive() {
+    let error = AccelOutputDataRate::try_from(3).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "invalid accelerometer output data rate: 3"
+    );
+}
+
+#[test]
+fn can_decode_raw_acceleration() {
+    let data = Acceleration::from_raw(
+        [0x10, 0x20, 0x30, 0x40, 0x50, 0xE0],
+        AccelMode::Normal,
+        AccelScale::G2,
+    );
+    assert_eq!(data.xyz_raw(), (0x2010, 0x4030, 0xE050));
+    assert_eq!(
+        data.xyz_left_justified(),
+        (0x2010, 0x4030, 0xE050_u16 as i16)
+    );
+    assert_eq!(data.xyz_mg(), (512, 1024, -504));
+}
+
+#[test]
+fn checks_accel_self_test_response() {
+    let accel_scaled = |x: i16, y: i16, z: i16, scale| {
+        let [x0, x1] = (x << 6).to_le_bytes();
+        let [y0, y1] = (y << 6).to_le_bytes();
+        let [z0, z1] = (z << 6).to_le_bytes();
+        Acceleration::from_raw([x0, x1, y0, y1, z0, z1], AccelMode::Normal, scale)
+    };
+    let accel = |x, y, z| accel_scaled(x, y, z, AccelScale::G2);
+
+    let before = accel(0, 0, -250);
+    assert!(accel_self_test_passed(&before, &accel(17, -17, -250 + 360)));
+    assert!(accel_self_test_passed(&before, &accel(100, 100, -150)));
+    assert!(!accel_self_test_passed(&before, &accel(16, 100, -150)));
+    assert!(!accel_self_test_passed(
+        &before,
+        &accel(100, 100, -250 + 361)
+    ));
+    assert!(!accel_self_test_passed(&before, &before));
+
+    let before = accel_scaled(0, 0, -125, AccelScale::G4);
+    assert!(accel_self_test_passed(
+        &before,
+        &accel_scaled(17, -17, -125 + 360, AccelScale::G4)
+    ));
+    assert!(!accel_self_test_passed(
+        &before,
+        &accel_scaled(16, 100, 0, AccelScale::G4)
+    ));
+    assert!(!accel_self_test_passed(
+        &accel(0, 0, -250),
+        &accel_scaled(17, -17, -125 + 360, AccelScale::G4)
+    ));
+}
+
+#[test]
+fn provides_accel_self_test_limits_per_scale() {
+    assert_eq!(accel_self_test_limits_mg(AccelScale::G2), (68, 1440));
+    assert_eq!(accel_self_test_limits_mg(AccelScale::G4), (136, 2880));
+    assert_eq!(accel_self_test_limits_mg(AccelScale::G8), (272, 5760));
+    assert_eq!(accel_self_test_limits_mg(AccelScale::G16), (544, 11520));
+}
+
+#[cfg(feature = "float")]
+#[test]
+fn can_convert_acceleration_to_float_units() {
+    use lsm303agr::{MeterPerSecSquared, G};
+
+    // 1024 mg in normal mode with ±2g scale.
+    let data = Acceleration::from_raw(
+        [0x00, 0x40, 0x00, 0xC0, 0x00, 0x00],
+        AccelMode::Normal,
+        AccelScale::G2,
+    );
+
+    let (x, y, z) = data.xyz_in::<G>();
+    assert_eq!((x, y, z), (G(1.024), G(-1.024), G(0.0)));
+
+    let (x, _, _) = data.xyz_in::<MeterPerSecSquared>();
+    assert!((x.0 - 10.042).abs() < 0.001);
+}
+
+#[test]
+fn can_detect_saturated_acceleration() {
+    // Normal mode, 10-bit values left-justified: 511, -512, 510
+    let data = Acceleration::from_raw(
+        [0xC0, 0x7F, 0x00, 0x80, 0x80, 0x7F],
+        AccelMode::Normal,
+        AccelScale::G2,
+    );
+    assert!(data.x_saturated());
+    assert!(data.y_saturated());
+    assert!(!data.z_saturated());
+    assert!(data.is_saturated());
+
+    let data = Acceleration::from_raw(
+        [0x00, 0x40, 0x00, 0xC0, 0x80, 0x7F],
+        AccelMode::Normal,
+        AccelScale::G2,
+    );
+    assert!(!data.is_saturated());
+
+    // High-resolution mode, 12-bit values left-justified: 2047
+    let data = Acceleration::from_raw(
+        [0x00, 0x00, 0x00, 0x00, 0xF0, 0x7F],
+        AccelMode::HighResolution,
+        AccelScale::G16,
+    );
+    assert!(data.z_saturated());
+}
+
+#[cfg(feature = "math")]
+#[test]
+fn can_compute_tilt_angles() {
+    let accel = |x: i16, y: i16, z: i16| {
+        let [x0, x1] = (x << 4).to_le_bytes();
+        let [y0, y1] = (y << 4).to_le_bytes();
+        let [z0, z1] = (z << 4).to_le_bytes();
+        Acceleration::from_raw(
+            [x0, x1, y0, y1, z0, z1],
+            AccelMode::HighResolution,
+            AccelScale::G2,
+        )
+    };
+    let assert_angle = |actual: f32, expected: f32| {
+        assert!(
+            (actual - expected).abs() < 0.01,
+            "{} != {}",
+            actual,
+            expected
+        );
+    };
+
+    // Lying flat.
+    let data = accel(0, 0, 1000);
+    assert_angle(data.pitch_deg(), 0.0);
+    assert_angle(data.roll_deg(), 0.0);
+
+    // Front raised by 30°.
+    let data = accel(500, 0, 866);
+    assert_angle(data.pitch_deg(), 30.0);
+    assert_angle(data.roll_deg(), 0.0);
+
+    // Left side raised by 45°.
+    let data = accel(0, 707, 707);
+    assert_angle(data.pitch_deg(), 0.0);
+    assert_angle(data.roll_deg(), 45.0);
+
+    // Upside down, right side raised.
+    let data = accel(0, -500, -866);
+    assert_angle(data.roll_deg(), -150.0);
+
+    // Standing on its back.
+    let data = accel(-1000, 0, 0);
+    assert_angle(data.pitch_deg(), -90.0);
+}
+
+#[test]
+fn can_decode_raw_magnetic_field() {
+    let data = MagneticField::from_raw([0x10, 0x20, 0x30, 0x40, 0x50, 0x60]);
+    assert_eq!(data.xyz_raw(), (0x2010, 0x4030, 0x6050));
+}
+
+#[test]
+fn can_create_zero_measurements() {
+    let data = Acceleration::zero(AccelMode::HighResolution, AccelScale::G8);
+    assert_eq!(data.xyz_mg(), (0, 0, 0));
+
+    assert_eq!(MagneticField::default().xyz_nt(), (0, 0, 0));
+}
+
+#[test]
+fn can_display_measurements() {
+    let data = Acceleration::from_raw(
+        [0x10, 0x20, 0x30, 0x40, 0x50, 0xE0],
+        AccelMode::Normal,
+        AccelScale::G2,
+    );
+    assert_eq!(format!("{}", data), "x=512mg y=1024mg z=-504mg");
+
+    let data = MagneticField::from_raw([0x01, 0x00, 0xFE, 0xFF, 0x2C, 0x01]);
+    assert_eq!(format!("{}", data), "x=150nT y=-300nT z=45000nT");
+}
@@ -0,0 +1,130 @@
+mod common;
+use crate::common::{
+    default_cs_n, BitFlags as BF, Register, ACCEL_ADDR, DEFAULT_CTRL_REG1_A, HZ50,
+};
+use embedded_hal_mock::{
+    delay::MockNoop as Delay,
+    i2c::{Mock as I2cMock, Transaction as I2cTrans},
+    pin::Mock as PinMock,
+    spi::{Mock as SpiMock, Transaction as SpiTrans},
+    MockError,
+};
+use lsm303agr::{
+    interface::{DynInterface, I2cInterface, RegisterAccess, SpiInterface},
+    AccelOutputDataRate, Error, Lsm303agr,
+};
+
+#[test]
+fn can_use_i2c_through_dyn_interface() {
+    let mut i2c = I2cInterface::new(I2cMock::new(&[
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::WHO_AM_I_A], vec![0x33]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::OUT_X_L_A | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        ),
+    ]));
+
+    let mut sensor = Lsm303agr::new_with_dyn(DynInterface::<MockError, ()>::new(&mut i2c));
+    assert!(sensor.accelerometer_id().unwrap().is_correct());
+    sensor
+        .set_accel_odr(&mut Delay, AccelOutputDataRate::Hz50)
+        .unwrap();
+    let data = sensor.acceleration().unwrap();
+    assert_eq!(data.xyz_raw(), (0x2010, 0x4030, 0x6050));
+
+    i2c.destroy().done();
+}
+
+#[test]
+fn can_use_spi_through_dyn_interface() {
+    let mut spi = SpiInterface::new(
+        SpiMock::new(&[
+            SpiTrans::transfer(vec![Register::WHO_AM_I_M | BF::SPI_RW, 0], vec![0, 0x40]),
+            SpiTrans::write(vec![Register::CFG_REG_A_M, 0]),
+            SpiTrans::transfer(
+                vec![
+                    Register::OUTX_L_REG_M | BF::SPI_MS | BF::SPI_RW,
+                    0,
+                    0,
+                    0,
+                    0,
+                    0,
+                    0,
+                ],
+                vec![0, 0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+            ),
+        ]),
+        PinMock::new(&[]),
+        default_cs_n(3),
+    );
+
+    let mut sensor = Lsm303agr::new_with_dyn(DynInterface::<MockError, MockError>::new(&mut spi));
+    assert!(sensor.magnetometer_id().unwrap().is_correct());
+    let mut sensor = sensor.into_mag_continuous().ok().unwrap();
+    let data = sensor.magnetic_field().unwrap();
+    assert_eq!(data.xyz_raw(), (0x2010, 0x4030, 0x6050));
+
+    let (mut spi, mut accel_cs, mut mag_cs) = spi.destroy();
+    spi.done();
+    accel_cs.done();
+    mag_cs.done();
+}
+
+#[test]
+fn can_select_boxed_interface_at_runtime() {
+    let mut i2c = I2cMock::new(&[I2cTrans::write_read(
+        ACCEL_ADDR,
+        vec![Register::WHO_AM_I_A],
+        vec![0x33],
+    )]);
+    let mut spi = SpiMock::new(&[SpiTrans::transfer(
+        vec![Register::WHO_AM_I_A | BF::SPI_RW, 0],
+        vec![0, 0x33],
+    )]);
+    let mut cs_accel = default_cs_n(1);
+    let mut cs_mag = PinMock::new(&[]);
+
+    for &use_spi in &[false, true] {
+        let mut iface: Box<dyn RegisterAccess<MockError, MockError>> = if use_spi {
+            Box::new(SpiInterface::new(
+                spi.clone(),
+                cs_accel.clone(),
+                cs_mag.clone(),
+            ))
+        } else {
+            Box::new(I2cInterface::new(i2c.clone()))
+        };
+        let mut sensor = Lsm303agr::new_with_dyn(DynInterface::new(iface.as_mut()));
+        assert!(sensor.accelerometer_id().unwrap().is_correct());
+    }
+
+    i2c.done();
+    spi.done();
+    cs_accel.done();
+    cs_mag.done();
+}
+
+#[test]
+fn rejects_reads_longer_than_6_bytes_on_both_buses() {
+    let mut i2c = I2cInterface::new(I2cMock::new(&[]));
+    let mut spi = SpiInterface::new(SpiMock::new(&[]), PinMock::new(&[]), PinMock::new(&[]));
+    let mut data = [0; 7];
+
+    let result: Result<(), Error<MockError, ()>> =
+        i2c.read_accel_bytes(Register::OUT_X_L_A, &mut data);
+    assert!(matches!(result, Err(Error::InvalidInputData)));
+    let result: Result<(), Error<MockError, MockError>> =
+        spi.read_mag_bytes(Register::OUTX_L_REG_M, &mut data);
+    assert!(matches!(result, Err(Error::InvalidInputData)));
+
+    i2c.destroy().done();
+    let (mut spi, mut accel_cs, mut mag_cs) = spi.destroy();
+    spi.done();
+    accel_cs.done();
+    mag_cs.done();
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "fake-interface")]
+
+use embedded_hal_mock::delay::MockNoop as Delay;
+use lsm303agr::{interface::FakeInterface, AccelOutputDataRate, Lsm303agr, MagOutputDataRate};
+
+#[test]
+fn can_read_ids() {
+    let mut sensor = Lsm303agr::new_with_fake(FakeInterface::new());
+    assert!(sensor.accelerometer_id().unwrap().is_correct());
+    assert!(sensor.magnetometer_id().unwrap().is_correct());
+}
+
+#[test]
+fn stores_writes() {
+    let mut sensor = Lsm303agr::new_with_fake(FakeInterface::new());
+    sensor.init().unwrap();
+    sensor
+        .set_accel_odr(&mut Delay, AccelOutputDataRate::Hz50)
+        .unwrap();
+    sensor
+        .set_mag_odr(&mut Delay, MagOutputDataRate::Hz50)
+        .unwrap();
+
+    let fake = sensor.destroy();
+    // CTRL_REG1_A
+    assert_eq!(fake.accel_register(0x20), 0b0100_0111);
+    // CTRL_REG4_A
+    assert_eq!(fake.accel_register(0x23), 0b1000_0000);
+    // CFG_REG_A_M
+    assert_eq!(fake.mag_register(0x60), 0b0000_1011);
+}
+
+#[test]
+fn can_read_acceleration() {
+    let mut fake = FakeInterface::new();
+    fake.set_acceleration_raw(0x2010, 0x4030, 0x6050);
+    let mut sensor = Lsm303agr::new_with_fake(fake);
+    sensor
+        .set_accel_odr(&mut Delay, AccelOutputDataRate::Hz50)
+        .unwrap();
+
+    let data = sensor.acceleration().unwrap();
+    assert_eq!(data.xyz_raw(), (0x2010, 0x4030, 0x6050));
+}
+
+#[test]
+fn can_read_magnetic_field() {
+    let mut sensor = Lsm303agr::new_with_fake(FakeInterface::new())
+        .into_mag_continuous()
+        .ok()
+        .unwrap();
+    sensor.fake_mut().set_magnetic_field_raw(1, 2, 3);
+
+    let data = sensor.magnetic_field().unwrap();
+    assert_eq!(data.xyz_raw(), (1, 2, 3));
+}
+
+#[test]
+fn can_dump_device() {
+    let mut sensor = Lsm303agr::new_with_fake(FakeInterface::new());
+    let snapshot = sensor.dump_device().unwrap();
+    assert_eq!(snapshot.accel_register(0x0F), Some(0x33));
+    assert_eq!(snapshot.mag_register(0x4F), Some(0x40));
+}
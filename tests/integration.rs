@@ -1,11 +1,14 @@
 mod common;
 use crate::common::{
     default_cs, default_cs_n, destroy_i2c, destroy_spi, new_i2c, new_spi, new_spi_accel,
-    new_spi_mag, BitFlags as BF, Register, ACCEL_ADDR, MAG_ADDR,
+    new_spi_mag, reset_i2c_transactions, BitFlags as BF, Register, ACCEL_ADDR,
+    ACCEL_RESET_DEFAULTS, DEFAULT_CFG_REG_A_M, DEFAULT_CTRL_REG1_A, HZ50, MAG_ADDR,
 };
 use embedded_hal_mock::{
-    i2c::Transaction as I2cTrans, pin::Mock as PinMock, spi::Transaction as SpiTrans,
+    delay::MockNoop as Delay, i2c::Transaction as I2cTrans, pin::Mock as PinMock,
+    spi::Transaction as SpiTrans,
 };
+use lsm303agr::{AccelMode, AccelOutputDataRate, AccelScale, Error, Lsm303agr};
 
 #[test]
 fn can_create_and_destroy_i2c() {
@@ -51,6 +54,20 @@ fn i2c_acc_id_is_correct() {
     destroy_i2c(sensor);
 }
 
+#[test]
+fn i2c_identity_is_correct() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::WHO_AM_I_A], vec![0x33]),
+        I2cTrans::write_read(MAG_ADDR, vec![Register::WHO_AM_I_M], vec![0x40]),
+    ]);
+    let (acc_id, mag_id) = sensor.identity().unwrap();
+
+    assert!(acc_id.is_correct());
+    assert!(mag_id.is_correct());
+
+    destroy_i2c(sensor);
+}
+
 #[test]
 fn i2c_mag_id_is_not_correct() {
     let mag_id = 0xAB;
@@ -183,3 +200,369 @@ fn can_init_spi() {
     sensor.init().unwrap();
     destroy_spi(sensor);
 }
+
+#[test]
+fn can_enable_spi_3_wire() {
+    let mut sensor = new_spi_accel(
+        &[SpiTrans::write(vec![Register::CTRL_REG4_A, BF::SPI_ENABLE])],
+        default_cs(),
+    );
+    sensor.enable_spi_3_wire().unwrap();
+    destroy_spi(sensor);
+}
+
+#[test]
+fn can_disable_i2c() {
+    let mut sensor = new_spi_mag(
+        &[SpiTrans::write(vec![Register::CFG_REG_C_M, BF::I2C_DIS])],
+        default_cs(),
+    );
+    sensor.disable_i2c().unwrap();
+    destroy_spi(sensor);
+}
+
+#[test]
+fn can_reset() {
+    let mut transactions = vec![
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, BF::ACCEL_BDU]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::TEMP_CFG_REG_A, BF::TEMP_EN1 | BF::TEMP_EN0],
+        ),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_C_M, BF::MAG_BDU]),
+    ];
+    transactions.extend(reset_i2c_transactions());
+    transactions.extend_from_slice(&[
+        // Cached registers are back to their defaults.
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, BF::ACCEL_BDU]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::TEMP_CFG_REG_A, BF::TEMP_EN1 | BF::TEMP_EN0],
+        ),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_C_M, BF::MAG_BDU]),
+    ]);
+    let mut sensor = new_i2c(&transactions);
+    sensor.init().unwrap();
+    sensor.reset(&mut Delay).unwrap();
+    sensor.init().unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn reset_restarts_one_shot_measurement() {
+    let mut transactions = vec![
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 1]),
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::OUTX_L_REG_M | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        ),
+    ];
+    transactions.extend(reset_i2c_transactions());
+    transactions.extend_from_slice(&[
+        // A new measurement is started after the reset.
+        I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![0]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 1]),
+    ]);
+    let mut sensor = new_i2c(&transactions);
+    sensor.magnetic_field_blocking(&mut Delay).unwrap();
+    sensor.reset(&mut Delay).unwrap();
+    assert!(matches!(
+        sensor.magnetic_field(),
+        Err(nb::Error::WouldBlock)
+    ));
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_check_reboot_complete() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::CTRL_REG5_A], vec![BF::BOOT]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::CTRL_REG5_A], vec![0]),
+    ]);
+    assert!(!sensor.acc_reboot_complete().unwrap());
+    assert!(sensor.acc_reboot_complete().unwrap());
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_sync_from_device() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A],
+            vec![DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::CTRL_REG2_A], vec![0]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::CTRL_REG3_A], vec![0]),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG4_A],
+            vec![BF::ACCEL_BDU | BF::HR],
+        ),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::CTRL_REG5_A], vec![0]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::CTRL_REG6_A], vec![0]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::TEMP_CFG_REG_A], vec![0]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::FIFO_CTRL_REG_A], vec![0]),
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::CFG_REG_A_M],
+            vec![DEFAULT_CFG_REG_A_M],
+        ),
+        I2cTrans::write_read(MAG_ADDR, vec![Register::CFG_REG_B_M], vec![0]),
+        I2cTrans::write_read(MAG_ADDR, vec![Register::CFG_REG_C_M], vec![BF::MAG_BDU]),
+        I2cTrans::write_read(MAG_ADDR, vec![Register::INT_CRTL_REG_M], vec![0]),
+        // Cached values are used for subsequent writes.
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG4_A, BF::ACCEL_BDU | BF::HR | (0b01 << 4)],
+        ),
+    ]);
+    sensor.sync_from_device().unwrap();
+    assert_eq!(sensor.get_accel_mode(), AccelMode::HighResolution);
+    sensor.set_accel_scale(AccelScale::G4).unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_restore_from_parts() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0b01 << 4]),
+        // Cached registers are restored from the saved configuration.
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0b01 << 4 | BF::HR]),
+    ]);
+    sensor
+        .set_accel_odr(&mut Delay, AccelOutputDataRate::Hz50)
+        .unwrap();
+    sensor.set_accel_scale(AccelScale::G4).unwrap();
+
+    let (iface, config) = sensor.into_parts();
+    let mut sensor = Lsm303agr::from_parts(iface, config);
+    assert_eq!(sensor.get_accel_odr(), Some(AccelOutputDataRate::Hz50));
+    assert_eq!(sensor.get_accel_scale(), AccelScale::G4);
+    sensor
+        .set_accel_mode(&mut Delay, AccelMode::HighResolution)
+        .unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_dump_cached_registers() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0b01 << 4]),
+    ]);
+    sensor
+        .set_accel_odr(&mut Delay, AccelOutputDataRate::Hz50)
+        .unwrap();
+    sensor.set_accel_scale(AccelScale::G4).unwrap();
+
+    let dump = sensor.debug_registers();
+    assert_eq!(dump.ctrl_reg1_a, DEFAULT_CTRL_REG1_A | HZ50);
+    assert_eq!(dump.ctrl_reg4_a, 0b01 << 4);
+    assert_eq!(dump.cfg_reg_a_m, DEFAULT_CFG_REG_A_M);
+    assert_eq!(dump.ctrl_reg2_a, 0);
+    assert!(format!("{:?}", dump).contains("ctrl_reg4_a: 16"));
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_dump_device() {
+    let accel = [0x07, 0x0C, 0x0D, 0x0E, 0x0F]
+        .iter()
+        .copied()
+        .chain(0x1F..=0x25)
+        .chain([0x27, 0x2E, 0x2F, 0x30, 0x32, 0x33, 0x34, 0x36, 0x37, 0x38])
+        .chain(0x3A..=0x3F);
+    let mag = (0x45..=0x4A)
+        .chain([0x4F, 0x60, 0x61, 0x62, 0x63])
+        .chain(0x65..=0x67);
+    let read = |i2c_addr: u8| {
+        move |address: u8| I2cTrans::write_read(i2c_addr, vec![address], vec![address])
+    };
+    let transactions: Vec<_> = accel
+        .map(read(ACCEL_ADDR))
+        .chain(mag.map(read(MAG_ADDR)))
+        .collect();
+    let mut sensor = new_i2c(&transactions);
+    let snapshot = sensor.dump_device().unwrap();
+    assert_eq!(snapshot.accel_register(0x0F), Some(0x0F));
+    assert_eq!(snapshot.accel_register(0x3F), Some(0x3F));
+    assert_eq!(snapshot.accel_register(0x10), None);
+    assert_eq!(snapshot.mag_register(0x4F), Some(0x4F));
+    assert_eq!(snapshot.mag_register(0x67), Some(0x67));
+    assert_eq!(snapshot.mag_register(0x4B), None);
+
+    // Registers with read side effects are skipped.
+    assert_eq!(snapshot.accel_register(Register::OUT_X_L_A), None);
+    assert_eq!(snapshot.accel_register(0x26), None);
+    assert_eq!(snapshot.accel_register(0x31), None);
+    assert_eq!(snapshot.mag_register(0x64), None);
+    assert_eq!(snapshot.mag_register(Register::OUTX_L_REG_M), None);
+
+    let debug = format!("{:?}", snapshot);
+    assert!(debug.contains("CTRL_REG1_A: 32"));
+    assert!(debug.contains("CFG_REG_A_M: 96"));
+    assert!(debug.contains("REFERENCE_A: not read"));
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_verify_writes() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0b01 << 4]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::CTRL_REG4_A], vec![0b01 << 4]),
+        // Start a single measurement, the mode bits are not verified.
+        I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![0]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 1]),
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::CFG_REG_A_M],
+            vec![DEFAULT_CFG_REG_A_M],
+        ),
+    ]);
+    sensor.set_write_verification(true);
+    sensor.set_accel_scale(AccelScale::G4).unwrap();
+    assert!(matches!(
+        sensor.magnetic_field(),
+        Err(nb::Error::WouldBlock)
+    ));
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_verify_reset_writes() {
+    let mut transactions = vec![
+        // Self-clearing bits are not verified.
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG5_A, BF::BOOT]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::CTRL_REG5_A], vec![0]),
+        I2cTrans::write(
+            MAG_ADDR,
+            vec![Register::CFG_REG_A_M, BF::SOFT_RST | DEFAULT_CFG_REG_A_M],
+        ),
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::CFG_REG_A_M],
+            vec![DEFAULT_CFG_REG_A_M],
+        ),
+        I2cTrans::write(
+            MAG_ADDR,
+            vec![Register::CFG_REG_A_M, BF::REBOOT | DEFAULT_CFG_REG_A_M],
+        ),
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::CFG_REG_A_M],
+            vec![DEFAULT_CFG_REG_A_M],
+        ),
+    ];
+    for &(reg, value) in ACCEL_RESET_DEFAULTS.iter() {
+        transactions.push(I2cTrans::write(ACCEL_ADDR, vec![reg, value]));
+        transactions.push(I2cTrans::write_read(ACCEL_ADDR, vec![reg], vec![value]));
+    }
+    let mut sensor = new_i2c(&transactions);
+    sensor.set_write_verification(true);
+    sensor.reset(&mut Delay).unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn write_verification_detects_mismatch() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0b01 << 4]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::CTRL_REG4_A], vec![0]),
+    ]);
+    sensor.set_write_verification(true);
+    assert!(matches!(
+        sensor.set_accel_scale(AccelScale::G4),
+        Err(Error::WriteVerification)
+    ));
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn reads_honour_big_endian_byte_order() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A],
+            vec![DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::CTRL_REG2_A], vec![0]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::CTRL_REG3_A], vec![0]),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG4_A],
+            vec![BF::ACCEL_BDU | BF::ACCEL_BLE],
+        ),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::CTRL_REG5_A], vec![0]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::CTRL_REG6_A], vec![0]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::TEMP_CFG_REG_A], vec![0]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::FIFO_CTRL_REG_A], vec![0]),
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::CFG_REG_A_M],
+            vec![DEFAULT_CFG_REG_A_M],
+        ),
+        I2cTrans::write_read(MAG_ADDR, vec![Register::CFG_REG_B_M], vec![0]),
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::CFG_REG_C_M],
+            vec![BF::MAG_BDU | BF::MAG_BLE],
+        ),
+        I2cTrans::write_read(MAG_ADDR, vec![Register::INT_CRTL_REG_M], vec![0]),
+        // Most significant bytes first.
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::OUT_X_L_A | 0x80],
+            vec![0x20, 0x10, 0x40, 0x30, 0x60, 0x50],
+        ),
+        I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![0]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 1]), // start measurement
+        I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![BF::XYZDR]),
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::OUTX_L_REG_M | 0x80],
+            vec![0x20, 0x10, 0x40, 0x30, 0x60, 0x50],
+        ),
+    ]);
+    sensor.sync_from_device().unwrap();
+
+    let acceleration = sensor.acceleration().unwrap();
+    assert_eq!(acceleration.xyz_raw(), (0x2010, 0x4030, 0x6050));
+    let field = nb::block!(sensor.magnetic_field()).unwrap();
+    assert_eq!(field.xyz_raw(), (0x2010, 0x4030, 0x6050));
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_display_errors() {
+    let error: Error<u8, ()> = Error::Comm(5);
+    assert_eq!(error.to_string(), "communication error: 5");
+    let error: Error<(), u8> = Error::Pin(3);
+    assert_eq!(error.to_string(), "chip-select pin error: 3");
+    let error: Error<(), ()> = Error::InvalidInputData;
+    assert_eq!(error.to_string(), "invalid input data");
+    let error: Error<(), ()> = Error::WriteVerification;
+    assert_eq!(error.to_string(), "register write verification failed");
+}
+
+#[test]
+fn spi_mode_is_mode_3() {
+    use embedded_hal::spi::{Phase, Polarity};
+
+    assert!(lsm303agr::SPI_MODE.polarity == Polarity::IdleHigh);
+    assert!(lsm303agr::SPI_MODE.phase == Phase::CaptureOnSecondTransition);
+    assert_eq!(lsm303agr::MAX_SPI_FREQ_HZ, 10_000_000);
+}
@@ -1,6 +1,10 @@
 mod common;
-use crate::common::{destroy_i2c, new_i2c, Register, MAG_ADDR};
-use embedded_hal_mock::i2c::Transaction as I2cTrans;
+use crate::common::{
+    destroy_i2c, new_i2c, Register, ACCEL_ADDR, DEFAULT_CFG_REG_A_M, DEFAULT_CTRL_REG1_A, MAG_ADDR,
+};
+use embedded_hal_mock::{delay::MockNoop as Delay, i2c::Transaction as I2cTrans, MockError};
+use lsm303agr::{AccelMode, AccelOutputDataRate, MagMode, MagOutputDataRate};
+use std::io::ErrorKind;
 
 #[test]
 fn can_change_into_continuous() {
@@ -19,3 +23,62 @@ fn can_change_into_one_shot() {
     let sensor = sensor.into_mag_one_shot().ok().unwrap();
     destroy_i2c(sensor);
 }
+
+#[test]
+fn changing_odr_in_continuous_mode_only_changes_odr_bits() {
+    let low_power = 0b1_0000;
+    let sensor = new_i2c(&[
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 0]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, low_power]),
+        // Mode bits are unchanged, only the ODR bits are set.
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, low_power | 0b1100]),
+    ]);
+    let mut sensor = sensor.into_mag_continuous().ok().unwrap();
+    sensor.set_mag_mode(&mut Delay, MagMode::LowPower).unwrap();
+    sensor
+        .set_mag_odr(&mut Delay, MagOutputDataRate::Hz100)
+        .unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn failed_mode_change_keeps_config() {
+    let sensor = new_i2c(&[
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 0])
+            .with_error(MockError::Io(ErrorKind::Other)),
+        // Still idle mode, only ODR changed.
+        I2cTrans::write(
+            MAG_ADDR,
+            vec![Register::CFG_REG_A_M, DEFAULT_CFG_REG_A_M | 0b1000],
+        ),
+    ]);
+    let mut sensor = sensor.into_mag_continuous().err().unwrap().dev;
+    sensor
+        .set_mag_odr(&mut Delay, MagOutputDataRate::Hz50)
+        .unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn mode_change_keeps_accel_odr() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, 9 << 4 | DEFAULT_CTRL_REG1_A],
+        ),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 0]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 0x3]),
+    ]);
+    sensor
+        .set_accel_odr(&mut Delay, AccelOutputDataRate::Khz1_344)
+        .unwrap();
+    let mut sensor = sensor.into_mag_continuous().ok().unwrap();
+    sensor
+        .set_accel_mode(&mut Delay, AccelMode::LowPower)
+        .expect_err("should have returned error");
+    let mut sensor = sensor.into_mag_one_shot().ok().unwrap();
+    sensor
+        .set_accel_mode(&mut Delay, AccelMode::LowPower)
+        .expect_err("should have returned error");
+    destroy_i2c(sensor);
+}
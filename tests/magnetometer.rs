@@ -10,7 +10,7 @@ use embedded_hal_mock::{
     pin::{Mock as PinMock, State as PinState, Transaction as PinTrans},
     spi::Transaction as SpiTrans,
 };
-use lsm303agr::{MagMode, MagOutputDataRate as ODR};
+use lsm303agr::{MagInterruptConfig, MagMode, MagOutputDataRate as ODR, NanoTesla};
 
 macro_rules! set_mag_odr {
     ($name:ident, $hz:ident, $value:expr) => {
@@ -21,6 +21,7 @@ macro_rules! set_mag_odr {
                 vec![Register::CFG_REG_A_M, $value | DEFAULT_CFG_REG_A_M],
             )]);
             sensor.set_mag_odr(&mut Delay, ODR::$hz).unwrap();
+            assert_eq!(sensor.get_mag_odr(), ODR::$hz);
             destroy_i2c(sensor);
         }
     };
@@ -57,18 +58,44 @@ fn can_change_mode() {
     destroy_i2c(sensor);
 }
 
+#[test]
+fn can_toggle_low_power() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            MAG_ADDR,
+            vec![Register::CFG_REG_A_M, DEFAULT_CFG_REG_A_M | 0b00010000],
+        ),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, DEFAULT_CFG_REG_A_M]),
+    ]);
+    sensor.mag_set_low_power(&mut Delay, true).unwrap();
+    assert_eq!(sensor.get_mag_mode(), MagMode::LowPower);
+
+    sensor.mag_set_low_power(&mut Delay, false).unwrap();
+    assert_eq!(sensor.get_mag_mode(), MagMode::HighResolution);
+
+    destroy_i2c(sensor);
+}
+
 macro_rules! assert_eq_xyz_nt {
     ($data:expr) => {{
         crate::assert_eq_xyz!($data, x_nt, y_nt, z_nt, xyz_nt);
     }};
 }
 
+#[test]
+fn can_check_mag_mode_type() {
+    let sensor = new_i2c(&[I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 0])]);
+    assert!(!sensor.is_mag_continuous());
+    let sensor = sensor.into_mag_continuous().ok().unwrap();
+    assert!(sensor.is_mag_continuous());
+    destroy_i2c(sensor);
+}
+
 #[test]
 fn can_take_one_shot_measurement_i2c() {
     let mut sensor = new_i2c(&[
         I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![0]),
-        I2cTrans::write_read(MAG_ADDR, vec![Register::CFG_REG_A_M], vec![0]), // idle
-        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 1]),            // start measurement
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 1]), // start measurement
         I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![0]),
         I2cTrans::write_read(MAG_ADDR, vec![Register::CFG_REG_A_M], vec![1]), // continue waiting
         I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![0xFF]),
@@ -127,6 +154,258 @@ fn can_take_continuous_measurement_i2c() {
     destroy_i2c(sensor);
 }
 
+#[test]
+fn can_recover_mag_from_saturation() {
+    let mut sensor = new_i2c(&[
+        // Enable low-pass filter
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_B_M, 0b1]),
+        I2cTrans::write(
+            MAG_ADDR,
+            vec![Register::CFG_REG_A_M, BF::REBOOT | DEFAULT_CFG_REG_A_M],
+        ),
+        // Cached configuration is written back.
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, DEFAULT_CFG_REG_A_M]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_B_M, 0b1]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_C_M, 0]),
+        I2cTrans::write(MAG_ADDR, vec![Register::INT_CRTL_REG_M, 0]),
+    ]);
+    sensor.mag_enable_low_pass_filter().unwrap();
+    sensor.mag_recover_from_saturation(&mut Delay).unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn recovering_mag_from_saturation_does_not_start_measurement() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 1]), // start measurement
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::OUTX_L_REG_M | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        ),
+        I2cTrans::write(MAG_ADDR, vec![Register::INT_CRTL_REG_M, 0b10100111]),
+        // Idle mode is written back instead of single mode.
+        I2cTrans::write(
+            MAG_ADDR,
+            vec![Register::CFG_REG_A_M, BF::REBOOT | DEFAULT_CFG_REG_A_M],
+        ),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, DEFAULT_CFG_REG_A_M]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_B_M, 0]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_C_M, 0]),
+        // Interrupt configuration is restored.
+        I2cTrans::write(MAG_ADDR, vec![Register::INT_CRTL_REG_M, 0b10100111]),
+        // The next measurement is started from scratch.
+        I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![0]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 1]),
+    ]);
+    sensor.magnetic_field_blocking(&mut Delay).unwrap();
+    sensor
+        .mag_configure_interrupt(MagInterruptConfig {
+            x_enabled: true,
+            y_enabled: false,
+            z_enabled: true,
+            active_high: true,
+            latched: true,
+            enabled: true,
+        })
+        .unwrap();
+    sensor.mag_recover_from_saturation(&mut Delay).unwrap();
+    assert!(sensor.magnetic_field().is_err());
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_clear_mag_overrun() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![BF::XYZOR]),
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::OUTX_L_REG_M | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        ),
+    ]);
+    assert!(sensor.mag_status().unwrap().xyz_overrun());
+    sensor.mag_clear_overrun().unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn one_shot_measurement_ignores_stale_data() {
+    let mut sensor = new_i2c(&[
+        // Stale data from a previous measurement is discarded.
+        I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![0xFF]),
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::OUTX_L_REG_M | 0x80],
+            vec![0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF],
+        ),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 1]), // start measurement
+        I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![0xFF]),
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::OUTX_L_REG_M | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        ),
+    ]);
+    assert!(matches!(
+        sensor.magnetic_field(),
+        Err(nb::Error::WouldBlock)
+    ));
+    let data = sensor.magnetic_field().unwrap();
+    assert_eq!(data.xyz_raw(), (0x2010, 0x4030, 0x6050));
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_put_mag_into_idle_mode() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![0]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 1]), // start measurement
+        I2cTrans::write_read(MAG_ADDR, vec![Register::CFG_REG_A_M], vec![1]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 0b11]), // idle
+        I2cTrans::write_read(MAG_ADDR, vec![Register::CFG_REG_A_M], vec![0b11]),
+    ]);
+    assert!(sensor.magnetic_field().is_err());
+    assert!(!sensor.mag_is_idle().unwrap());
+    sensor.mag_idle().unwrap();
+    assert!(sensor.mag_is_idle().unwrap());
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn mag_idle_discards_unread_data() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![0]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 1]), // start measurement
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 0b11]), // idle
+        // Data of the stopped measurement is discarded and a new one is started.
+        I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![BF::XYZDR]),
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::OUTX_L_REG_M | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        ),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 1]),
+    ]);
+    assert!(matches!(
+        sensor.magnetic_field(),
+        Err(nb::Error::WouldBlock)
+    ));
+    sensor.mag_idle().unwrap();
+    assert!(matches!(
+        sensor.magnetic_field(),
+        Err(nb::Error::WouldBlock)
+    ));
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_take_blocking_one_shot_measurement_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 1]), // start measurement
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::OUTX_L_REG_M | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        ),
+    ]);
+    let data = sensor.magnetic_field_blocking(&mut Delay).unwrap();
+
+    assert_eq!(data.xyz_raw(), (0x2010, 0x4030, 0x6050));
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_get_raw_magnetic_field() {
+    let mut sensor = new_i2c(&[I2cTrans::write_read(
+        MAG_ADDR,
+        vec![Register::OUTX_L_REG_M | 0x80],
+        vec![0x10, 0x20, 0x30, 0x40, 0x50, 0xE0],
+    )]);
+    let (x, y, z) = sensor.magnetic_field_raw().unwrap();
+
+    assert_eq!(x, 0x2010);
+    assert_eq!(y, 0x4030);
+    assert_eq!(z, 0xE050_u16 as i16);
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn mag_reads_honour_byte_order() {
+    let sensor = new_i2c(&[
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 0]),
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::OUTX_L_REG_M | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        ),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_C_M, BF::MAG_BLE]),
+        // Most significant bytes first.
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::OUTX_L_REG_M | 0x80],
+            vec![0x20, 0x10, 0x40, 0x30, 0x60, 0x50],
+        ),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_C_M, 0]),
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::OUTX_L_REG_M | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        ),
+    ]);
+    let mut sensor = sensor.into_mag_continuous().ok().unwrap();
+
+    let field = sensor.magnetic_field().unwrap();
+    assert_eq!(field.xyz_raw(), (0x2010, 0x4030, 0x6050));
+
+    sensor.mag_set_big_endian(true).unwrap();
+    let field = sensor.magnetic_field().unwrap();
+    assert_eq!(field.xyz_raw(), (0x2010, 0x4030, 0x6050));
+
+    sensor.mag_set_big_endian(false).unwrap();
+    let field = sensor.magnetic_field().unwrap();
+    assert_eq!(field.xyz_raw(), (0x2010, 0x4030, 0x6050));
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_check_mag_data_ready() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![0]),
+        I2cTrans::write_read(MAG_ADDR, vec![Register::STATUS_REG_M], vec![BF::XYZDR]),
+    ]);
+    assert!(!sensor.mag_data_ready().unwrap());
+    assert!(sensor.mag_data_ready().unwrap());
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_take_typed_continuous_measurement_i2c() {
+    let sensor = new_i2c(&[
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 0]),
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::OUTX_L_REG_M | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        ),
+    ]);
+    let mut sensor = sensor.into_mag_continuous().ok().unwrap();
+    let (x, y, z) = sensor.magnetic_field_typed().unwrap();
+
+    assert_eq!(x, NanoTesla(0x2010 * 150));
+    assert_eq!(y, NanoTesla(0x4030 * 150));
+    assert_eq!(z, NanoTesla(0x6050 * 150));
+
+    assert_eq!(z - y, NanoTesla((0x6050 - 0x4030) * 150));
+    assert_eq!(i32::from(x + y), (0x2010 + 0x4030) * 150);
+
+    destroy_i2c(sensor);
+}
+
 #[test]
 fn can_take_continuous_measurement_spi() {
     let sensor = new_spi_mag(
@@ -273,3 +552,133 @@ fn can_disable_mag_low_pass_filter() {
 
     destroy_i2c(sensor);
 }
+
+#[test]
+fn can_set_mag_pulse_frequency() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_B_M, 0b100]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_B_M, 0]),
+    ]);
+    sensor.mag_set_pulse_frequency(false).unwrap();
+    sensor.mag_set_pulse_frequency(true).unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_enable_mag_data_ready_pin() {
+    let mut sensor = new_i2c(&[I2cTrans::write(
+        MAG_ADDR,
+        vec![Register::CFG_REG_C_M, 0b00000001],
+    )]);
+    sensor.mag_enable_drdy_pin().unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+#[allow(deprecated)]
+fn can_enable_mag_data_ready_pin_with_deprecated_method() {
+    let mut sensor = new_i2c(&[I2cTrans::write(
+        MAG_ADDR,
+        vec![Register::CFG_REG_C_M, 0b00000001],
+    )]);
+    sensor.mag_enable_int().unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_enable_mag_threshold_interrupt() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(MAG_ADDR, vec![Register::INT_CRTL_REG_M, 0b00000001]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_C_M, 0b01000000]),
+        I2cTrans::write(MAG_ADDR, vec![Register::INT_CRTL_REG_M, 0]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_C_M, 0]),
+    ]);
+    sensor.mag_enable_threshold_interrupt(true).unwrap();
+    sensor.mag_enable_threshold_interrupt(false).unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_route_mag_interrupt_to_pin() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_C_M, 0b01000000]),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_C_M, 0]),
+    ]);
+    sensor.mag_route_interrupt_to_pin(true).unwrap();
+    sensor.mag_route_interrupt_to_pin(false).unwrap();
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_configure_mag_interrupt() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(MAG_ADDR, vec![Register::INT_CRTL_REG_M, 0b10100111]),
+        I2cTrans::write(MAG_ADDR, vec![Register::INT_CRTL_REG_M, 0b01000000]),
+    ]);
+
+    sensor
+        .mag_configure_interrupt(MagInterruptConfig {
+            x_enabled: true,
+            y_enabled: false,
+            z_enabled: true,
+            active_high: true,
+            latched: true,
+            enabled: true,
+        })
+        .expect("failed to configure interrupt");
+
+    sensor
+        .mag_configure_interrupt(MagInterruptConfig {
+            y_enabled: true,
+            ..Default::default()
+        })
+        .expect("failed to configure interrupt");
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_set_mag_interrupt_polarity_and_latch() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(MAG_ADDR, vec![Register::INT_CRTL_REG_M, 0b11100001]),
+        I2cTrans::write(MAG_ADDR, vec![Register::INT_CRTL_REG_M, 0b11100101]),
+        I2cTrans::write(MAG_ADDR, vec![Register::INT_CRTL_REG_M, 0b11100111]),
+        I2cTrans::write(MAG_ADDR, vec![Register::INT_CRTL_REG_M, 0b11100011]),
+    ]);
+
+    sensor
+        .mag_configure_interrupt(MagInterruptConfig {
+            x_enabled: true,
+            y_enabled: true,
+            z_enabled: true,
+            enabled: true,
+            ..Default::default()
+        })
+        .unwrap();
+    sensor.mag_set_interrupt_polarity(true).unwrap();
+    sensor.mag_set_interrupt_latched(true).unwrap();
+    sensor.mag_set_interrupt_polarity(false).unwrap();
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_get_mag_interrupt_source() {
+    let mut sensor = new_i2c(&[I2cTrans::write_read(
+        MAG_ADDR,
+        vec![Register::INT_SOURCE_REG_M],
+        vec![0b10001011],
+    )]);
+
+    let source = sensor.mag_interrupt_source().unwrap();
+    assert!(source.x_positive());
+    assert!(!source.y_positive());
+    assert!(!source.z_positive());
+    assert!(!source.x_negative());
+    assert!(source.y_negative());
+    assert!(!source.z_negative());
+    assert!(source.measurement_range_overflow());
+    assert!(source.interrupt_occurred());
+
+    destroy_i2c(sensor);
+}
@@ -0,0 +1,81 @@
+mod common;
+use crate::common::{
+    destroy_i2c, new_i2c, BitFlags as BF, Register, ACCEL_ADDR, DEFAULT_CFG_REG_A_M,
+    DEFAULT_CTRL_REG1_A, MAG_ADDR,
+};
+use embedded_hal_mock::{delay::MockNoop as Delay, i2c::Transaction as I2cTrans};
+use lsm303agr::{AccelMode, AccelScale, MagMode};
+
+#[test]
+fn can_apply_high_performance_preset() {
+    let sensor = new_i2c(&[
+        // 100 Hz
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | 5 << 4],
+        ),
+        // High-resolution mode
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | 5 << 4],
+        ),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, BF::HR]),
+        // ±2g
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, BF::HR]),
+        // High-resolution mode
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, DEFAULT_CFG_REG_A_M]),
+        // 50 Hz
+        I2cTrans::write(
+            MAG_ADDR,
+            vec![Register::CFG_REG_A_M, DEFAULT_CFG_REG_A_M | 2 << 2],
+        ),
+        // Continuous mode
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 2 << 2]),
+    ]);
+    let mut sensor = sensor.preset_high_performance(&mut Delay).ok().unwrap();
+
+    assert_eq!(sensor.get_accel_mode(), AccelMode::HighResolution);
+    assert_eq!(sensor.get_accel_scale(), AccelScale::G2);
+    assert_eq!(sensor.get_mag_mode(), MagMode::HighResolution);
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_apply_low_power_preset() {
+    let mut sensor = new_i2c(&[
+        // 10 Hz
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | 2 << 4],
+        ),
+        // Low-power mode
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![
+                Register::CTRL_REG1_A,
+                DEFAULT_CTRL_REG1_A | 2 << 4 | BF::LP_EN,
+            ],
+        ),
+        // ±2g
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0]),
+        // Low-power mode
+        I2cTrans::write(
+            MAG_ADDR,
+            vec![Register::CFG_REG_A_M, DEFAULT_CFG_REG_A_M | 1 << 4],
+        ),
+        // 10 Hz
+        I2cTrans::write(
+            MAG_ADDR,
+            vec![Register::CFG_REG_A_M, DEFAULT_CFG_REG_A_M | 1 << 4],
+        ),
+    ]);
+    sensor.preset_low_power(&mut Delay).unwrap();
+
+    assert_eq!(sensor.get_accel_mode(), AccelMode::LowPower);
+    assert_eq!(sensor.get_accel_scale(), AccelScale::G2);
+    assert_eq!(sensor.get_mag_mode(), MagMode::LowPower);
+
+    destroy_i2c(sensor);
+}
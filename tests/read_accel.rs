@@ -1,12 +1,12 @@
 mod common;
 use crate::common::{
-    default_cs_n, destroy_i2c, destroy_spi, new_i2c, new_spi_accel, BitFlags as BF, Register,
-    ACCEL_ADDR, DEFAULT_CTRL_REG1_A, HZ50,
+    default_cs_n, destroy_i2c, destroy_spi, new_i2c, new_spi_accel, BitFlags as BF, RecordingDelay,
+    Register, ACCEL_ADDR, DEFAULT_CTRL_REG1_A, HZ50, MAG_ADDR,
 };
 use embedded_hal_mock::{
     delay::MockNoop as Delay, i2c::Transaction as I2cTrans, spi::Transaction as SpiTrans,
 };
-use lsm303agr::{AccelMode, AccelOutputDataRate, AccelScale};
+use lsm303agr::{AccelMode, AccelOutputDataRate, AccelScale, Axis, FifoMode, MilliG};
 
 fn i2c_mode_txns(mode: &AccelMode) -> Vec<I2cTrans> {
     match mode {
@@ -335,3 +335,633 @@ fn can_get_12_bit_data_i2c() {
 
     destroy_i2c(sensor);
 }
+
+#[test]
+fn can_get_typed_data_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, BF::HR]),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::OUT_X_L_A | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        ),
+    ]);
+    sensor
+        .set_accel_odr(&mut Delay, AccelOutputDataRate::Hz50)
+        .unwrap();
+    sensor
+        .set_accel_mode(&mut Delay, AccelMode::HighResolution)
+        .unwrap();
+    let (x, y, z) = sensor.acceleration_typed().unwrap();
+
+    assert_eq!(x, MilliG(0x2010 / (1 << 4)));
+    assert_eq!(y, MilliG(0x4030 / (1 << 4)));
+    assert_eq!(z, MilliG(0x6050 / (1 << 4)));
+
+    assert_eq!(y - x, MilliG(0x4030 / (1 << 4) - 0x2010 / (1 << 4)));
+    assert_eq!(x + y, MilliG(0x2010 / (1 << 4) + 0x4030 / (1 << 4)));
+    assert_eq!(i32::from(z), 0x6050 / (1 << 4));
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_get_data_in_unit_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::OUT_X_L_A | 0x80],
+            vec![0x00, 0x40, 0x00, 0xC0, 0x00, 0x00],
+        ),
+    ]);
+    sensor
+        .set_accel_odr(&mut Delay, AccelOutputDataRate::Hz50)
+        .unwrap();
+    let (x, y, z) = sensor.acceleration_in::<MilliG>().unwrap();
+
+    assert_eq!((x, y, z), (MilliG(1024), MilliG(-1024), MilliG(0)));
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_get_data_in_mm_per_s2_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::OUT_X_L_A | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        ),
+    ]);
+    sensor
+        .set_accel_odr(&mut Delay, AccelOutputDataRate::Hz50)
+        .unwrap();
+    let data = sensor.acceleration().unwrap();
+
+    assert_eq!(data.xyz_mg(), (512, 1024, 1540));
+    assert_eq!(data.xyz_mm_per_s2(), (5021, 10042, 15102));
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_get_single_axis_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![(Register::OUT_X_L_A + 2) | 0x80],
+            vec![0x30, 0x40],
+        ),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![(Register::OUT_X_L_A + 4) | 0x80],
+            vec![0x50, 0xE0],
+        ),
+    ]);
+    sensor
+        .set_accel_odr(&mut Delay, AccelOutputDataRate::Hz50)
+        .unwrap();
+
+    assert_eq!(sensor.acceleration_axis(Axis::Y).unwrap(), 1024);
+    assert_eq!(sensor.acceleration_axis(Axis::Z).unwrap(), -504);
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn cannot_get_single_axis_when_powered_down() {
+    let mut sensor = new_i2c(&[]);
+    assert!(sensor.acceleration_axis(Axis::X).is_err());
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_get_raw_data_i2c() {
+    let mut sensor = new_i2c(&[I2cTrans::write_read(
+        ACCEL_ADDR,
+        vec![Register::OUT_X_L_A | 0x80],
+        vec![0x10, 0x20, 0x30, 0x40, 0x50, 0xE0],
+    )]);
+    let (x, y, z) = sensor.acceleration_raw().unwrap();
+
+    assert_eq!(x, 0x2010);
+    assert_eq!(y, 0x4030);
+    assert_eq!(z, 0xE050_u16 as i16);
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_get_data_with_timestamp_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::OUT_X_L_A | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        ),
+    ]);
+    sensor
+        .set_accel_odr(&mut Delay, AccelOutputDataRate::Hz50)
+        .unwrap();
+    let mut ticks = 41;
+    let (data, timestamp) = sensor
+        .acceleration_with_timestamp(|| {
+            ticks += 1;
+            ticks
+        })
+        .unwrap();
+
+    assert_eq!(data.xyz_raw(), (0x2010, 0x4030, 0x6050));
+    assert_eq!(timestamp, 42);
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_read_batch_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::STATUS_REG_A], vec![BF::XYZDR]),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::OUT_X_L_A | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        ),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::STATUS_REG_A], vec![0]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::STATUS_REG_A], vec![BF::XYZDR]),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::OUT_X_L_A | 0x80],
+            vec![0x20, 0x30, 0x40, 0x50, 0x60, 0x70],
+        ),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::STATUS_REG_A], vec![0]),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::STATUS_REG_A], vec![0]),
+    ]);
+    sensor
+        .set_accel_odr(&mut Delay, AccelOutputDataRate::Hz50)
+        .unwrap();
+
+    let mut samples = [None; 3];
+    let mut delay = RecordingDelay::default();
+    let count = sensor.acceleration_batch(&mut samples, &mut delay).unwrap();
+
+    assert_eq!(count, 2);
+    // One sample period at 50 Hz, each time no new data is available.
+    assert_eq!(delay.delays_us, [20_000, 20_000]);
+    assert_eq!(samples[0].unwrap().xyz_raw(), (0x2010, 0x4030, 0x6050));
+    assert_eq!(samples[1].unwrap().xyz_raw(), (0x3020, 0x5040, 0x7060));
+    assert!(samples[2].is_none());
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn cannot_read_batch_when_powered_down() {
+    let mut sensor = new_i2c(&[]);
+    let mut samples = [None; 3];
+    sensor
+        .acceleration_batch(&mut samples, &mut Delay)
+        .expect_err("should have returned error");
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_iterate_over_fifo_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::FIFO_SRC_REG_A], vec![2]),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::OUT_X_L_A | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        ),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::FIFO_SRC_REG_A], vec![1]),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::OUT_X_L_A | 0x80],
+            vec![0x20, 0x30, 0x40, 0x50, 0x60, 0x70],
+        ),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::FIFO_SRC_REG_A],
+            vec![BF::FIFO_EMPTY],
+        ),
+    ]);
+    sensor
+        .set_accel_odr(&mut Delay, AccelOutputDataRate::Hz50)
+        .unwrap();
+    let samples = sensor
+        .acc_fifo_iter()
+        .map(|sample| sample.unwrap().xyz_raw())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        samples,
+        vec![(0x2010, 0x4030, 0x6050), (0x3020, 0x5040, 0x7060)]
+    );
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_iterate_over_fifo_samples_with_magnetic_field_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 0]),
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::OUTX_L_REG_M | 0x80],
+            vec![0x01, 0x02, 0x03, 0x04, 0x05, 0x06],
+        ),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::FIFO_SRC_REG_A], vec![2]),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::OUT_X_L_A | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        ),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::FIFO_SRC_REG_A], vec![1]),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::OUT_X_L_A | 0x80],
+            vec![0x20, 0x30, 0x40, 0x50, 0x60, 0x70],
+        ),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::FIFO_SRC_REG_A],
+            vec![BF::FIFO_EMPTY],
+        ),
+    ]);
+    sensor
+        .set_accel_odr(&mut Delay, AccelOutputDataRate::Hz50)
+        .unwrap();
+    let mut sensor = sensor.into_mag_continuous().ok().unwrap();
+
+    let samples = sensor
+        .fifo_samples()
+        .unwrap()
+        .map(|sample| {
+            let (acceleration, field) = sample.unwrap();
+            (acceleration.xyz_raw(), field.xyz_raw())
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        samples,
+        vec![
+            ((0x2010, 0x4030, 0x6050), (0x0201, 0x0403, 0x0605)),
+            ((0x3020, 0x5040, 0x7060), (0x0201, 0x0403, 0x0605)),
+        ]
+    );
+    destroy_i2c(sensor);
+}
+
+fn stream_mode_txns() -> Vec<I2cTrans> {
+    vec![
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG5_A, 0b01000000]),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::FIFO_CTRL_REG_A, 0b10010000]),
+    ]
+}
+
+#[test]
+fn can_drain_stream_i2c() {
+    let mut transactions = stream_mode_txns();
+    transactions.extend([
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::FIFO_SRC_REG_A], vec![2]),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::OUT_X_L_A | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        ),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::OUT_X_L_A | 0x80],
+            vec![0x20, 0x30, 0x40, 0x50, 0x60, 0x70],
+        ),
+    ]);
+    let mut sensor = new_i2c(&transactions);
+    sensor
+        .set_accel_odr(&mut Delay, AccelOutputDataRate::Hz50)
+        .unwrap();
+    sensor.acc_set_fifo_mode(FifoMode::Stream, 16).unwrap();
+
+    let mut samples = Vec::new();
+    let drain = sensor
+        .acc_stream_drain(|sample| samples.push(sample.xyz_raw()))
+        .unwrap();
+
+    assert_eq!(drain.samples, 2);
+    assert!(!drain.overrun);
+    assert_eq!(
+        samples,
+        vec![(0x2010, 0x4030, 0x6050), (0x3020, 0x5040, 0x7060)]
+    );
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_recover_from_stream_overrun_i2c() {
+    let mut transactions = stream_mode_txns();
+    transactions.push(I2cTrans::write_read(
+        ACCEL_ADDR,
+        vec![Register::FIFO_SRC_REG_A],
+        vec![BF::FIFO_OVRN | 0b11111],
+    ));
+    transactions.extend((0..32).map(|_| {
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::OUT_X_L_A | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        )
+    }));
+    transactions.extend([
+        // Bypass mode
+        I2cTrans::write(ACCEL_ADDR, vec![Register::FIFO_CTRL_REG_A, 0b00010000]),
+        // Stream mode
+        I2cTrans::write(ACCEL_ADDR, vec![Register::FIFO_CTRL_REG_A, 0b10010000]),
+    ]);
+    let mut sensor = new_i2c(&transactions);
+    sensor
+        .set_accel_odr(&mut Delay, AccelOutputDataRate::Hz50)
+        .unwrap();
+    sensor.acc_set_fifo_mode(FifoMode::Stream, 16).unwrap();
+
+    let mut count = 0;
+    let drain = sensor.acc_stream_drain(|_| count += 1).unwrap();
+
+    assert_eq!(drain.samples, 32);
+    assert!(drain.overrun);
+    assert_eq!(count, 32);
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_read_fifo_status_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::FIFO_SRC_REG_A], vec![0]),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::FIFO_SRC_REG_A],
+            vec![BF::FIFO_WTM | 16],
+        ),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::FIFO_SRC_REG_A], vec![16]),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::FIFO_SRC_REG_A],
+            vec![BF::FIFO_EMPTY],
+        ),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::FIFO_SRC_REG_A],
+            vec![BF::FIFO_WTM | BF::FIFO_OVRN | 0b11111],
+        ),
+    ]);
+    assert!(!sensor.acc_fifo_watermark_reached().unwrap());
+    assert!(sensor.acc_fifo_watermark_reached().unwrap());
+    assert_eq!(sensor.acc_fifo_sample_count().unwrap(), 16);
+    assert_eq!(sensor.acc_fifo_sample_count().unwrap(), 0);
+    assert_eq!(sensor.acc_fifo_sample_count().unwrap(), 32);
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn cannot_drain_stream_when_not_in_stream_mode() {
+    let mut sensor = new_i2c(&[]);
+    sensor
+        .acc_stream_drain(|_| {})
+        .expect_err("should have returned error");
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_peek_latest_acceleration_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::OUT_X_L_A | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        ),
+    ]);
+    sensor
+        .set_accel_odr(&mut Delay, AccelOutputDataRate::Hz50)
+        .unwrap();
+    let data = sensor.acc_peek_latest().unwrap();
+    assert_eq!(data.xyz_raw(), (0x2010, 0x4030, 0x6050));
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn cannot_peek_latest_acceleration_when_fifo_enabled() {
+    let mut sensor = new_i2c(&stream_mode_txns());
+    sensor
+        .set_accel_odr(&mut Delay, AccelOutputDataRate::Hz50)
+        .unwrap();
+    sensor.acc_set_fifo_mode(FifoMode::Stream, 16).unwrap();
+    sensor
+        .acc_peek_latest()
+        .expect_err("should have returned error");
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_read_acceleration_with_status_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::STATUS_REG_A],
+            vec![BF::XYZDR | BF::XYZOR],
+        ),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::OUT_X_L_A | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        ),
+    ]);
+    sensor
+        .set_accel_odr(&mut Delay, AccelOutputDataRate::Hz50)
+        .unwrap();
+
+    let (data, status) = sensor.acceleration_with_status().unwrap();
+    assert_eq!(data.xyz_raw(), (0x2010, 0x4030, 0x6050));
+    assert!(status.xyz_new_data());
+    assert!(status.xyz_overrun());
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_read_checked_acceleration_i2c() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::OUT_X_L_A | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        ),
+        // Overwritten during read.
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::STATUS_REG_A],
+            vec![BF::XYZDR | BF::XYZOR],
+        ),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::OUT_X_L_A | 0x80],
+            vec![0x20, 0x30, 0x40, 0x50, 0x60, 0x70],
+        ),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::STATUS_REG_A], vec![0]),
+    ]);
+    sensor
+        .set_accel_odr(&mut Delay, AccelOutputDataRate::Hz50)
+        .unwrap();
+
+    let data = sensor.acceleration_checked(1).unwrap();
+    assert_eq!(data.xyz_raw(), (0x3020, 0x5040, 0x7060));
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn checked_acceleration_retries_when_overrun_was_already_set() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::STATUS_REG_A],
+            vec![BF::XYZDR | BF::XYZOR],
+        ),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::OUT_X_L_A | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        ),
+        // Reading the output registers clears the overrun, so it is set again
+        // because they were overwritten during the read.
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::STATUS_REG_A],
+            vec![BF::XYZDR | BF::XYZOR],
+        ),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::OUT_X_L_A | 0x80],
+            vec![0x20, 0x30, 0x40, 0x50, 0x60, 0x70],
+        ),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::STATUS_REG_A], vec![0]),
+    ]);
+    sensor
+        .set_accel_odr(&mut Delay, AccelOutputDataRate::Hz50)
+        .unwrap();
+
+    assert!(sensor.accel_status().unwrap().xyz_overrun());
+    let data = sensor.acceleration_checked(1).unwrap();
+    assert_eq!(data.xyz_raw(), (0x3020, 0x5040, 0x7060));
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn checked_acceleration_does_not_retry_on_new_data() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::OUT_X_L_A | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        ),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::STATUS_REG_A], vec![BF::XYZDR]),
+    ]);
+    sensor
+        .set_accel_odr(&mut Delay, AccelOutputDataRate::Hz50)
+        .unwrap();
+
+    let data = sensor.acceleration_checked(0).unwrap();
+    assert_eq!(data.xyz_raw(), (0x2010, 0x4030, 0x6050));
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn checked_acceleration_would_block_when_always_updated() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::OUT_X_L_A | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        ),
+        I2cTrans::write_read(ACCEL_ADDR, vec![Register::STATUS_REG_A], vec![BF::XYZOR]),
+    ]);
+    sensor
+        .set_accel_odr(&mut Delay, AccelOutputDataRate::Hz50)
+        .unwrap();
+
+    assert!(matches!(
+        sensor.acceleration_checked(0),
+        Err(nb::Error::WouldBlock)
+    ));
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn cannot_read_acceleration_when_powered_down() {
+    let mut sensor = new_i2c(&[]);
+    sensor
+        .acceleration()
+        .expect_err("should have returned error");
+    destroy_i2c(sensor);
+}
@@ -1,12 +1,12 @@
 mod common;
 use crate::common::{
-    default_cs_n, destroy_i2c, destroy_spi, new_i2c, new_spi_accel, BitFlags as BF, Register,
-    ACCEL_ADDR, DEFAULT_CTRL_REG1_A, HZ50,
+    default_cs, default_cs_n, destroy_i2c, destroy_spi, new_i2c, new_spi, BitFlags as BF, Register,
+    ACCEL_ADDR, DEFAULT_CTRL_REG1_A, HZ50, MAG_ADDR,
 };
 use embedded_hal_mock::{
     delay::MockNoop as Delay, i2c::Transaction as I2cTrans, spi::Transaction as SpiTrans,
 };
-use lsm303agr::AccelOutputDataRate;
+use lsm303agr::{AccelMode, AccelOutputDataRate, Error, TempSource};
 
 #[test]
 fn can_read_temperature_has_new_data() {
@@ -47,6 +47,12 @@ fn can_read_temperature_has_no_new_data() {
 #[test]
 fn can_read_temperature_i2c() {
     let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, BF::ACCEL_BDU]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::TEMP_CFG_REG_A, BF::TEMP_EN1 | BF::TEMP_EN0],
+        ),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_C_M, BF::MAG_BDU]),
         I2cTrans::write(
             ACCEL_ADDR,
             vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
@@ -58,13 +64,18 @@ fn can_read_temperature_i2c() {
         ),
     ]);
 
+    sensor.init().unwrap();
     sensor
         .set_accel_odr(&mut Delay, AccelOutputDataRate::Hz50)
         .unwrap();
     let data = sensor.temperature().unwrap();
 
     assert_eq!(data.raw(), 0xe2b3);
+    assert_eq!(data.resolution_bits(), 10);
     assert_eq!(data.unscaled(), -7501);
+    assert_eq!(data.degrees_celsius_milli(), -4300);
+    assert_eq!(format!("{}", data), "-4.3°C");
+    #[cfg(feature = "float")]
     assert_eq!((data.degrees_celsius() * 10.0).round() / 10.0, -4.3);
 
     destroy_i2c(sensor);
@@ -72,17 +83,22 @@ fn can_read_temperature_i2c() {
 
 #[test]
 fn can_read_temperature_spi() {
-    let mut sensor = new_spi_accel(
+    let mut sensor = new_spi(
         &[
+            SpiTrans::write(vec![Register::CTRL_REG4_A, BF::ACCEL_BDU]),
+            SpiTrans::write(vec![Register::TEMP_CFG_REG_A, BF::TEMP_EN1 | BF::TEMP_EN0]),
+            SpiTrans::write(vec![Register::CFG_REG_C_M, BF::MAG_BDU]),
             SpiTrans::write(vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50]),
             SpiTrans::transfer(
                 vec![Register::OUT_TEMP_L_A | BF::SPI_RW | BF::SPI_MS, 0, 0],
                 vec![0, 0x10, 0x20],
             ),
         ],
-        default_cs_n(2),
+        default_cs_n(4),
+        default_cs(),
     );
 
+    sensor.init().unwrap();
     sensor
         .set_accel_odr(&mut Delay, AccelOutputDataRate::Hz50)
         .unwrap();
@@ -90,7 +106,110 @@ fn can_read_temperature_spi() {
 
     assert_eq!(data.raw(), 0x2010);
     assert_eq!(data.unscaled(), 8208);
+    assert_eq!(data.degrees_celsius_milli(), 57062);
+    assert_eq!(format!("{}", data), "57.1°C");
+    #[cfg(feature = "float")]
     assert_eq!((data.degrees_celsius() * 10.0).round() / 10.0, 57.1);
 
     destroy_spi(sensor);
 }
+
+#[test]
+fn temperature_has_8_bit_resolution_in_low_power_mode() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, BF::ACCEL_BDU]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::TEMP_CFG_REG_A, BF::TEMP_EN1 | BF::TEMP_EN0],
+        ),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_C_M, BF::MAG_BDU]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, BF::ACCEL_BDU]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![
+                Register::CTRL_REG1_A,
+                DEFAULT_CTRL_REG1_A | BF::LP_EN | HZ50,
+            ],
+        ),
+        I2cTrans::write_read(
+            ACCEL_ADDR,
+            vec![Register::OUT_TEMP_L_A | 0x80],
+            vec![0x00, 0x0F],
+        ),
+    ]);
+
+    sensor.init().unwrap();
+    sensor
+        .set_accel_odr(&mut Delay, AccelOutputDataRate::Hz50)
+        .unwrap();
+    sensor
+        .set_accel_mode(&mut Delay, AccelMode::LowPower)
+        .unwrap();
+    let data = sensor.temperature().unwrap();
+
+    assert_eq!(data.resolution_bits(), 8);
+    assert_eq!(data.degrees_celsius_milli(), 40_000);
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn cannot_read_temperature_when_disabled() {
+    let mut sensor = new_i2c(&[]);
+
+    assert_eq!(sensor.acc_temperature_source(), TempSource::Disabled);
+    assert!(matches!(sensor.temperature(), Err(Error::InvalidInputData)));
+
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn temperature_source_is_sensor_after_init() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, BF::ACCEL_BDU]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::TEMP_CFG_REG_A, BF::TEMP_EN1 | BF::TEMP_EN0],
+        ),
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_C_M, BF::MAG_BDU]),
+    ]);
+
+    sensor.init().unwrap();
+    assert_eq!(sensor.acc_temperature_source(), TempSource::Sensor);
+
+    destroy_i2c(sensor);
+}
+
+macro_rules! temperature_test {
+    ($name:ident, $data:expr, $unscaled:expr, $milli_celsius:expr) => {
+        #[test]
+        fn $name() {
+            let mut sensor = new_i2c(&[
+                I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, BF::ACCEL_BDU]),
+                I2cTrans::write(
+                    ACCEL_ADDR,
+                    vec![Register::TEMP_CFG_REG_A, BF::TEMP_EN1 | BF::TEMP_EN0],
+                ),
+                I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_C_M, BF::MAG_BDU]),
+                I2cTrans::write_read(ACCEL_ADDR, vec![Register::OUT_TEMP_L_A | 0x80], $data),
+            ]);
+            sensor.init().unwrap();
+            let data = sensor.temperature().unwrap();
+
+            assert_eq!(data.unscaled(), $unscaled);
+            assert_eq!(data.degrees_celsius_milli(), $milli_celsius);
+            #[cfg(feature = "float")]
+            assert_eq!(data.degrees_celsius(), $milli_celsius as f32 / 1000.0);
+
+            destroy_i2c(sensor);
+        }
+    };
+}
+temperature_test!(temperature_25_celsius, vec![0x00, 0x00], 0, 25_000);
+temperature_test!(temperature_40_celsius, vec![0x00, 0x0F], 0x0F00, 40_000);
+temperature_test!(temperature_10_celsius, vec![0x00, 0xF1], -0x0F00, 10_000);
+temperature_test!(temperature_10_bit, vec![0xC0, 0x0F], 0x0FC0, 40_750);
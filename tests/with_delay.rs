@@ -0,0 +1,67 @@
+mod common;
+use crate::common::{
+    destroy_i2c, new_i2c, reset_i2c_transactions, BitFlags as BF, Register, ACCEL_ADDR,
+    DEFAULT_CFG_REG_A_M, DEFAULT_CTRL_REG1_A, HZ50, MAG_ADDR,
+};
+use embedded_hal_mock::{delay::MockNoop as Delay, i2c::Transaction as I2cTrans};
+use lsm303agr::{AccelMode, AccelOutputDataRate, MagMode, MagOutputDataRate};
+
+#[test]
+fn can_configure_with_stored_delay() {
+    let mut sensor = new_i2c(&[
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![Register::CTRL_REG1_A, DEFAULT_CTRL_REG1_A | HZ50],
+        ),
+        I2cTrans::write(ACCEL_ADDR, vec![Register::CTRL_REG4_A, 0]),
+        I2cTrans::write(
+            ACCEL_ADDR,
+            vec![
+                Register::CTRL_REG1_A,
+                DEFAULT_CTRL_REG1_A | BF::LP_EN | HZ50,
+            ],
+        ),
+        I2cTrans::write(
+            MAG_ADDR,
+            vec![Register::CFG_REG_A_M, DEFAULT_CFG_REG_A_M | 1 << 2],
+        ),
+        I2cTrans::write(
+            MAG_ADDR,
+            vec![
+                Register::CFG_REG_A_M,
+                DEFAULT_CFG_REG_A_M | 0b00010000 | 1 << 2,
+            ],
+        ),
+    ]);
+
+    let mut configured = sensor.with_delay(Delay);
+    configured.set_accel_odr(AccelOutputDataRate::Hz50).unwrap();
+    configured.set_accel_mode(AccelMode::LowPower).unwrap();
+    configured.set_mag_odr(MagOutputDataRate::Hz20).unwrap();
+    configured.set_mag_mode(MagMode::LowPower).unwrap();
+    let _delay: Delay = configured.release();
+
+    assert_eq!(sensor.get_accel_mode(), AccelMode::LowPower);
+    destroy_i2c(sensor);
+}
+
+#[test]
+fn can_measure_and_reset_with_stored_delay() {
+    let mut transactions = vec![
+        I2cTrans::write(MAG_ADDR, vec![Register::CFG_REG_A_M, 1]), // start measurement
+        I2cTrans::write_read(
+            MAG_ADDR,
+            vec![Register::OUTX_L_REG_M | 0x80],
+            vec![0x10, 0x20, 0x30, 0x40, 0x50, 0x60],
+        ),
+    ];
+    transactions.extend(reset_i2c_transactions());
+    let mut sensor = new_i2c(&transactions);
+
+    let mut configured = sensor.with_delay(Delay);
+    let data = configured.magnetic_field_blocking().unwrap();
+    assert_eq!(data.xyz_raw(), (0x2010, 0x4030, 0x6050));
+    configured.reset().unwrap();
+
+    destroy_i2c(sensor);
+}